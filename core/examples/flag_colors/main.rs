@@ -0,0 +1,31 @@
+//! Writes `data/flag_colors.json` from the flags in `assets/flags/4x3`.
+//! Run again after changing the flag assets:
+//! `cargo run -p geo_game_core --release --example flag_colors`
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use geo_game_core::flagcolor::FlagColors;
+
+mod render;
+
+fn main() {
+    let root: &Path = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    let mut files: Vec<PathBuf> = fs::read_dir(root.join("assets/flags/4x3"))
+        .expect("Failed to read flags directory")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "svg"))
+        .collect();
+    files.sort();
+
+    let mut colors: FlagColors = FlagColors::default();
+    for path in &files {
+        let svg: String = fs::read_to_string(path).expect("Failed to read flag");
+        let name: &str = path.file_name().unwrap().to_str().unwrap();
+        colors.insert(name, render::analyze_svg(&svg));
+    }
+
+    let out: PathBuf = root.join("data/flag_colors.json");
+    fs::write(&out, serde_json::to_string_pretty(&colors).unwrap()).expect("Failed to write flag colors");
+    println!("{} flags written to {}", files.len(), out.display());
+}
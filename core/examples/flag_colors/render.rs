@@ -0,0 +1,33 @@
+//! Renders a flag with resvg to measure its colours.
+
+use geo_game_core::flagcolor::{self, ColorShare, Rgb};
+use resvg::{tiny_skia, usvg};
+
+//size of the rendered flag
+const WIDTH: u32 = 80;
+const HEIGHT: u32 = 60;
+//pixels more transparent than this are not counted
+const MIN_ALPHA: u8 = 128;
+
+//dominant colours of the svg, the largest first
+pub fn analyze_svg(svg: &str) -> Vec<ColorShare> {
+    //no anti-aliasing, so the edges do not add blended colours
+    let options: usvg::Options = usvg::Options {
+        shape_rendering: usvg::ShapeRendering::CrispEdges,
+        ..usvg::Options::default()
+    };
+    let tree: usvg::Tree = usvg::Tree::from_str(svg, &options).expect("Failed to parse flag");
+    let size: tiny_skia::Size = tree.size();
+    let transform: tiny_skia::Transform =
+        tiny_skia::Transform::from_scale(WIDTH as f32 / size.width(), HEIGHT as f32 / size.height());
+    let mut pixmap: tiny_skia::Pixmap = tiny_skia::Pixmap::new(WIDTH, HEIGHT).unwrap();
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+
+    let cells: Vec<Option<Rgb>> = pixmap
+        .pixels()
+        .iter()
+        .map(|pixel| pixel.demultiply())
+        .map(|pixel| (pixel.alpha() >= MIN_ALPHA).then_some(Rgb { r: pixel.red(), g: pixel.green(), b: pixel.blue() }))
+        .collect();
+    flagcolor::color_shares(&cells)
+}
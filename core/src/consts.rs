@@ -0,0 +1,31 @@
+//default number of answers in game
+pub const ANSWER_NUM: usize = 4;
+//selectable numbers of answers
+pub const ANSWER_NUMS: [usize; 4] = [2, 4, 6, 8];
+//answers removed by the 50/50 help out of ANSWER_NUM
+pub const BAD_ANSWER: usize = 2;
+//ticks of the question timer
+pub const TIMER_TICKS: u32 = 60;
+//selectable ticks of the question timer
+pub const TIMER_CHOICES: [u32; 5] = [30, 45, 60, 90, 120];
+
+pub mod difficulty {
+//ticks of the question timer
+    pub const EASY_TICKS: u32 = 90;
+    pub const HARD_TICKS: u32 = 30;
+//number of answers on hard
+    pub const HARD_ANSWERS: usize = 6;
+}
+
+pub mod score {
+//points for right answer
+    pub const CORRECT: u32 = 100;
+//points for every remaining timer tick
+    pub const TICK: u32 = 5;
+//penalty for 50/50 help
+    pub const HELP_PENALTY: u32 = 50;
+//points for every earlier right answer in a row in the higher or lower questions
+    pub const STREAK: u32 = 10;
+//longest streak that still raises the bonus
+    pub const STREAK_MAX: u32 = 10;
+}
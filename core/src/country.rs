@@ -0,0 +1,253 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+pub enum Continent {
+    Africa,
+    Asia,
+    Europe,
+#[serde(rename = "North America")]
+    NorthAmerica,
+#[serde(rename = "South America")]
+    SouthAmerica,
+    Oceania,
+#[serde(other)]
+    Other,
+}
+
+//UN M49 subregions, Africa and the Americas by the intermediate regions
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum Subregion {
+#[serde(rename = "Eastern Europe")]
+    EasternEurope,
+#[serde(rename = "Northern Europe")]
+    NorthernEurope,
+#[serde(rename = "Southern Europe")]
+    SouthernEurope,
+#[serde(rename = "Western Europe")]
+    WesternEurope,
+#[serde(rename = "Central Asia")]
+    CentralAsia,
+#[serde(rename = "Eastern Asia")]
+    EasternAsia,
+#[serde(rename = "South-eastern Asia")]
+    SouthEasternAsia,
+#[serde(rename = "Southern Asia")]
+    SouthernAsia,
+#[serde(rename = "Western Asia")]
+    WesternAsia,
+#[serde(rename = "Northern Africa")]
+    NorthernAfrica,
+#[serde(rename = "Eastern Africa")]
+    EasternAfrica,
+#[serde(rename = "Middle Africa")]
+    MiddleAfrica,
+#[serde(rename = "Southern Africa")]
+    SouthernAfrica,
+#[serde(rename = "Western Africa")]
+    WesternAfrica,
+#[serde(rename = "Northern America")]
+    NorthernAmerica,
+#[serde(rename = "Central America")]
+    CentralAmerica,
+    Caribbean,
+#[serde(rename = "South America")]
+    SouthAmerica,
+#[serde(rename = "Australia and New Zealand")]
+    AustraliaAndNewZealand,
+    Melanesia,
+    Micronesia,
+    Polynesia,
+}
+
+//subregions in the order of the subregion checkboxes, grouped as CONTINENTS
+pub const SUBREGIONS: [Subregion; 22] = {
+    use Subregion::*;
+    [
+        EasternEurope, NorthernEurope, SouthernEurope, WesternEurope,
+        CentralAsia, EasternAsia, SouthEasternAsia, SouthernAsia, WesternAsia,
+        NorthernAfrica, EasternAfrica, MiddleAfrica, SouthernAfrica, WesternAfrica,
+        NorthernAmerica, CentralAmerica, Caribbean,
+        SouthAmerica,
+        AustraliaAndNewZealand, Melanesia, Micronesia, Polynesia,
+    ]
+};
+
+#[derive(Debug, Clone)]
+pub struct ContinentsTranslation {
+    pub eur: String,
+    pub asi: String,
+    pub afr: String,
+    pub nam: String,
+    pub sam: String,
+    pub oce: String,
+    pub other: String
+}
+
+impl Continent {
+    pub fn ret_continent_name(&self, tr: &ContinentsTranslation) -> String {
+        use Continent::*;
+        match self {
+            Africa => &tr.afr,
+            Asia => &tr.asi,
+            Europe => &tr.eur,
+            NorthAmerica => &tr.nam,
+            SouthAmerica => &tr.sam,
+            Oceania => &tr.oce,
+            Other => &tr.other
+        }.to_string()
+    }
+}
+
+#[derive(Debug, Deserialize, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum DrivingSide {
+    Left,
+    Right,
+}
+
+//name is localized, code is the ISO 4217 one shared by every user of the currency
+#[derive(Debug, Deserialize, PartialEq, Clone)]
+pub struct Currency {
+    pub code: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Country {
+#[serde(default)]
+    pub alt_capitals: Vec<String>,
+#[serde(default)]
+    pub alt_names: Vec<String>,
+//km²
+#[serde(default)]
+    pub area: Option<f64>,
+//codes of the countries with a land border
+#[serde(default)]
+    pub borders: Vec<String>,
+#[serde(default)]
+    pub calling_code: Option<String>,
+    pub capital: Option<String>,
+    pub code: String,
+    pub continent: Option<Continent>,
+#[serde(default)]
+    pub currency: Option<Currency>,
+#[serde(default)]
+    pub driving_side: Option<DrivingSide>,
+    pub flag_4x3: String,
+    pub iso: bool,
+#[serde(default)]
+    pub languages: Vec<String>,
+    pub name: String,
+#[serde(default)]
+    pub population: Option<u64>,
+#[serde(default)]
+    pub subregion: Option<Subregion>,
+}
+
+//codes of the international organisations among the flags
+const ORGANISATIONS: [&str; 7] = ["arab", "asean", "cefta", "eac", "eu", "pc", "un"];
+
+//placeholder flag of an unknown place
+const UNKNOWN: &str = "xx";
+
+impl Country {
+    //organisations are not iso and belong to no continent
+    pub fn is_organisation(&self) -> bool {
+        !self.iso && ORGANISATIONS.contains(&self.code.as_str())
+    }
+
+    //places without a continent, like Antarctica or remote islands, are in Other
+    pub fn region(&self) -> Continent {
+        self.continent.clone().unwrap_or(Continent::Other)
+    }
+}
+
+//entries asked besides the sovereign states
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy)]
+pub struct IsoFilter {
+    pub territories: bool,
+    pub organisations: bool,
+}
+
+impl Default for IsoFilter {
+    fn default() -> Self {
+        IsoFilter { territories: true, organisations: false }
+    }
+}
+
+impl IsoFilter {
+    pub fn accepts(&self, country: &Country) -> bool {
+        match (country.iso, country.is_organisation()) {
+            (true, _) => true,
+            (false, true) => self.organisations,
+            (false, false) => self.territories,
+        }
+    }
+}
+
+//any mix of whole continents and subregions
+#[derive(Debug, Clone, Default)]
+pub struct Regions {
+    pub continents: Vec<Continent>,
+    pub subregions: Vec<Subregion>,
+}
+
+impl Regions {
+    pub fn continents(continents: &[Continent]) -> Self {
+        Regions { continents: continents.to_vec(), subregions: Vec::new() }
+    }
+
+    //organisations span the continents, so they are in every region
+    pub fn contains(&self, country: &Country) -> bool {
+        country.is_organisation()
+            || self.continents.contains(&country.region())
+            || country.subregion.is_some_and(|subregion| self.subregions.contains(&subregion))
+    }
+}
+
+pub fn filter_by_regions(countries: &[Country], regions: &Regions, iso: IsoFilter) -> Vec<Country> {
+    countries
+        .iter()
+        .filter(|country| iso.accepts(country) && country.code != UNKNOWN)
+        .filter(|country| regions.contains(country))
+        .cloned()
+        .collect()
+}
+
+pub fn find_by_code<'a>(countries: &'a [Country], code: &str) -> Option<&'a Country> {
+    countries.iter().find(|country| country.code == code)
+}
+
+//continents in the order of the region checkboxes
+pub const CONTINENTS: [Continent; 7] = {
+    use Continent::*;
+    [Europe, Asia, Africa, NorthAmerica, SouthAmerica, Oceania, Other]
+};
+
+pub fn create_continents_list(input_config: &[bool]) -> Vec<Continent> {
+    let mut out = Vec::new();
+
+    for i in 0..CONTINENTS.len() {
+        if input_config[i] {
+            out.push(CONTINENTS[i].clone());
+        }
+    }
+    out
+}
+
+pub fn create_subregions_list(input_config: &[bool]) -> Vec<Subregion> {
+    SUBREGIONS
+        .iter()
+        .zip(input_config)
+        .filter(|(_, checked)| **checked)
+        .map(|(subregion, _)| *subregion)
+        .collect()
+}
+
+//regions of the continent and subregion checkboxes
+pub fn create_regions(continents: &[bool], subregions: &[bool]) -> Regions {
+    Regions {
+        continents: create_continents_list(continents),
+        subregions: create_subregions_list(subregions),
+    }
+}
@@ -0,0 +1,35 @@
+use crate::gamelogic::GameMode;
+
+//modes of the daily challenge, fixed so new modes do not change the game of a date
+const DAILY_MODES: [GameMode; 3] = [GameMode::Flags, GameMode::Capitals, GameMode::Fandc];
+
+//daily challenge, every player gets the same game for the same date
+#[derive(Debug, PartialEq, Clone)]
+pub struct Daily {
+    pub seed: u64,
+    pub modes: Vec<GameMode>,
+}
+
+impl Daily {
+    pub fn from_date(year: i32, month: u32, day: u32) -> Self {
+        let date: u64 = (year as u64) * 10000 + (month as u64) * 100 + day as u64;
+        let seed: u64 = mix(date);
+        //non zero mask of the modes
+        let mask: u64 = seed % ((1 << DAILY_MODES.len()) - 1) + 1;
+        let modes: Vec<GameMode> = DAILY_MODES
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| mask & (1 << bit) != 0)
+            .map(|(_, mode)| mode.clone())
+            .collect();
+        Daily { seed, modes }
+    }
+}
+
+//splitmix64, spreads close dates to unrelated seeds
+fn mix(value: u64) -> u64 {
+    let mut z: u64 = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
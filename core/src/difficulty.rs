@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::consts::{difficulty::*, ANSWER_NUM, TIMER_TICKS};
+use crate::gamelogic::Similarity;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy, Default)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    //timer, answers and distractors from the settings
+    Custom,
+}
+
+pub const DIFFICULTIES: [Difficulty; 4] =
+    [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard, Difficulty::Custom];
+
+//what a difficulty changes in the game
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Rules {
+    pub ticks: u32,
+    pub answers: usize,
+    //50/50 help can be used
+    pub help: bool,
+    //the iso filter of the player is used, otherwise only sovereign states are asked
+    pub territories: bool,
+    pub similarity: Similarity,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            ticks: TIMER_TICKS,
+            answers: ANSWER_NUM,
+            help: true,
+            territories: true,
+            similarity: Similarity::Random,
+        }
+    }
+}
+
+impl Difficulty {
+    pub fn rules(&self, custom: &Rules) -> Rules {
+        match self {
+            Difficulty::Easy => Rules {
+                ticks: EASY_TICKS,
+                territories: false,
+                ..Rules::default()
+            },
+            Difficulty::Normal => Rules::default(),
+            Difficulty::Hard => Rules {
+                ticks: HARD_TICKS,
+                answers: HARD_ANSWERS,
+                help: false,
+                similarity: Similarity::Continent,
+                ..Rules::default()
+            },
+            Difficulty::Custom => *custom,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::country::Country;
+
+//colours below this share of the flag are not dominant
+const MIN_SHARE: f32 = 0.01;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Hash)]
+#[serde(into = "String", try_from = "String")]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    //`#rgb` or `#rrggbb`
+    pub fn parse(text: &str) -> Option<Rgb> {
+        let hex: &str = text.trim().strip_prefix('#')?;
+        let digits: Vec<u8> = hex
+            .chars()
+            .map(|ch| ch.to_digit(16).map(|digit| digit as u8))
+            .collect::<Option<Vec<u8>>>()?;
+        match digits.as_slice() {
+            [r, g, b] => Some(Rgb { r: r * 17, g: g * 17, b: b * 17 }),
+            [r1, r2, g1, g2, b1, b2] => Some(Rgb { r: r1 * 16 + r2, g: g1 * 16 + g2, b: b1 * 16 + b2 }),
+            _ => None,
+        }
+    }
+
+    //0.0 for the same colour, 1.0 for black and white
+    pub fn distance(&self, other: &Rgb) -> f32 {
+        let dr: f32 = self.r as f32 - other.r as f32;
+        let dg: f32 = self.g as f32 - other.g as f32;
+        let db: f32 = self.b as f32 - other.b as f32;
+        (dr * dr + dg * dg + db * db).sqrt() / (3.0 * 255.0 * 255.0_f32).sqrt()
+    }
+}
+
+impl From<Rgb> for String {
+    fn from(rgb: Rgb) -> String {
+        format!("#{:02x}{:02x}{:02x}", rgb.r, rgb.g, rgb.b)
+    }
+}
+
+impl TryFrom<String> for Rgb {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Rgb, String> {
+        Rgb::parse(&text).ok_or(format!("unknown colour {text}"))
+    }
+}
+
+//one colour of a flag and the part of its area
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
+pub struct ColorShare {
+    pub color: Rgb,
+    pub share: f32,
+}
+
+//dominant colours of the pixels of a flag, the largest first, transparent pixels are `None`
+pub fn color_shares(cells: &[Option<Rgb>]) -> Vec<ColorShare> {
+    let mut counts: Vec<(Rgb, usize)> = Vec::new();
+    for color in cells.iter().flatten() {
+        match counts.iter_mut().find(|(rgb, _)| rgb == color) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((*color, 1)),
+        }
+    }
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+        .into_iter()
+        .map(|(color, count)| ColorShare {
+            color,
+            //three digits are enough and keep the data file small
+            share: (count as f32 / cells.len() as f32 * 1000.0).round() / 1000.0,
+        })
+        .filter(|color| color.share >= MIN_SHARE)
+        .collect()
+}
+
+//share weighted distance from every colour to the closest one of the other flag
+pub fn palette_distance(a: &[ColorShare], b: &[ColorShare]) -> f32 {
+    fn one_way(from: &[ColorShare], to: &[ColorShare]) -> f32 {
+        let total: f32 = from.iter().map(|x| x.share).sum();
+        let sum: f32 = from
+            .iter()
+            .map(|x| x.share * to.iter().map(|y| x.color.distance(&y.color)).fold(1.0, f32::min))
+            .sum();
+        sum / total
+    }
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+    (one_way(a, b) + one_way(b, a)) / 2.0
+}
+
+//precomputed colours of every flag by `flag_4x3` file
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[serde(transparent)]
+pub struct FlagColors {
+    flags: BTreeMap<String, Vec<ColorShare>>,
+}
+
+impl FlagColors {
+    pub fn insert(&mut self, flag: &str, colors: Vec<ColorShare>) {
+        self.flags.insert(flag.to_string(), colors);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.flags.len()
+    }
+
+    //empty when the flag was not analyzed
+    pub fn of(&self, country: &Country) -> &[ColorShare] {
+        self.flags.get(&country.flag_4x3).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    pub fn dominant(&self, country: &Country) -> Option<Rgb> {
+        self.of(country).first().map(|color| color.color)
+    }
+
+    //0.0 for flags of the same colours, 1.0 when unknown
+    pub fn distance(&self, a: &Country, b: &Country) -> f32 {
+        palette_distance(self.of(a), self.of(b))
+    }
+
+    //other countries with the most alike flags, the closest first
+    pub fn similar<'a>(&self, country: &Country, countries: &'a [Country], count: usize) -> Vec<&'a Country> {
+        let mut others: Vec<(f32, &Country)> = countries
+            .iter()
+            .filter(|other| other.code != country.code && !self.of(other).is_empty())
+            .map(|other| (self.distance(country, other), other))
+            .collect();
+        others.sort_by(|a, b| a.0.total_cmp(&b.0));
+        others.into_iter().take(count).map(|(_, other)| other).collect()
+    }
+}
@@ -0,0 +1,632 @@
+use rand::seq::{IteratorRandom, SliceRandom};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use crate::consts::{ANSWER_NUM, BAD_ANSWER};
+use crate::country::{Continent, ContinentsTranslation, Country, CONTINENTS};
+use crate::flagcolor::FlagColors;
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum GameMode {
+    Flags,
+    Capitals,
+    Fandc,
+    ReverseFlags,
+    ReverseCapitals,
+    Continents,
+    Codes,
+    Typing,
+    Currencies,
+    Neighbours,
+    Compare
+}
+
+//number of modes in the mode checkboxes
+pub const MODE_NUM: usize = 11;
+
+//what is shown to the player above the answers
+#[derive(Debug, PartialEq, Clone)]
+pub enum Prompt {
+    Flag(String),
+    Text(String),
+    //nothing above, the two flag options are compared
+    Versus,
+}
+
+//what the player is asked about the prompt
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Ask {
+    #[default]
+    Answer,
+    Borders,
+    NotBorders,
+    LargerPopulation,
+    LargerArea,
+}
+
+#[derive(Debug, Clone)]
+pub struct Question {
+    pub mode: GameMode,
+    pub prompt: Prompt,
+    pub ask: Ask,
+    //country the question is about, the right option of a comparison
+    pub target: Country,
+    pub options: Vec<String>,
+    //flag files when the answers are shown as flags
+    pub option_flags: Option<Vec<String>>,
+    pub answer: usize,
+    //free text answer, `options` holds only the right answer
+    pub typed: bool,
+    //other accepted spellings of the typed answer
+    pub accepted: Vec<String>,
+}
+
+impl Question {
+    pub fn check(&self, index: usize) -> bool {
+        index == self.answer
+    }
+
+    pub fn check_text(&self, text: &str) -> bool {
+        matches_answer(text, &self.options[self.answer], &self.accepted)
+    }
+
+    //with two options or less the help would leave only the right answer
+    pub fn allows_help(&self) -> bool {
+        !self.typed && self.mode != GameMode::Compare && self.options.len() > 2
+    }
+}
+
+//target countries of one game session, dealt without replacement
+#[derive(Default)]
+pub struct Deck {
+    cards: Vec<usize>,
+    size: usize,
+}
+
+impl Deck {
+    pub fn new<R: Rng>(size: usize, rng: &mut R) -> Self {
+        let mut deck = Deck { cards: Vec::new(), size };
+        deck.shuffle(rng);
+        deck
+    }
+
+    fn shuffle<R: Rng>(&mut self, rng: &mut R) {
+        self.cards = (0..self.size).collect();
+        self.cards.shuffle(rng);
+    }
+
+    //deal the next card accepted by the filter, reshuffle only when the pool is exhausted
+    pub fn deal<R: Rng, F: Fn(usize) -> bool>(&mut self, rng: &mut R, accept: F) -> Option<usize> {
+        if self.cards.is_empty() {
+            self.shuffle(rng);
+        }
+        let pos: usize = self.cards.iter().rposition(|&card| accept(card))?;
+        Some(self.cards.swap_remove(pos))
+    }
+
+    pub fn holds<F: Fn(usize) -> bool>(&self, accept: F) -> bool {
+        self.cards.iter().any(|&card| accept(card))
+    }
+}
+
+//random seed for a session without a fixed one
+pub fn new_seed() -> u64 {
+    rand::random()
+}
+
+pub fn seeded_rng(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
+}
+
+pub fn get_distractors<R: Rng>(countries: &[Country], target: &Country, count: usize, rng: &mut R) -> Vec<Country> {
+    countries
+    .iter()
+    .filter(|country| country.code != target.code)
+    .choose_multiple(rng, count)
+    .into_iter()
+    .cloned()
+    .collect()
+}
+
+//how wrong answers are chosen
+#[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy, Default)]
+pub enum Similarity {
+    #[default]
+    Random,
+    Continent,
+    Colours,
+    Spelling,
+}
+
+pub const SIMILARITIES: [Similarity; 4] =
+    [Similarity::Random, Similarity::Continent, Similarity::Colours, Similarity::Spelling];
+
+#[derive(Debug, Clone, Default)]
+pub struct Distractors {
+    pub similarity: Similarity,
+    pub colors: FlagColors,
+}
+
+impl Distractors {
+    //lower is more alike, equal keys are picked at random
+    fn key(&self, target: &Country, country: &Country) -> u32 {
+        match self.similarity {
+            Similarity::Random => 0,
+            Similarity::Continent => (country.continent != target.continent) as u32,
+            Similarity::Colours => {
+                (self.colors.distance(target, country) * 20.0) as u32
+            }
+            Similarity::Spelling => {
+                let a: Vec<char> = normalize_answer(&target.name).chars().collect();
+                let b: Vec<char> = normalize_answer(&country.name).chars().collect();
+                edit_distance(&a, &b) as u32
+            }
+        }
+    }
+
+    //flags of the same colours, like France and Réunion, can not be told apart
+    fn looks_the_same(&self, target: &Country, country: &Country) -> bool {
+        self.similarity == Similarity::Colours
+            && (country.flag_4x3 == target.flag_4x3 || self.colors.distance(target, country) == 0.0)
+    }
+
+    pub fn pick<R: Rng>(&self, countries: &[Country], target: &Country, count: usize, rng: &mut R) -> Vec<Country> {
+        if self.similarity == Similarity::Random {
+            return get_distractors(countries, target, count, rng);
+        }
+        let mut candidates: Vec<&Country> = countries
+            .iter()
+            .filter(|country| country.code != target.code && !self.looks_the_same(target, country))
+            .collect();
+        candidates.shuffle(rng);
+        candidates.sort_by_cached_key(|country| self.key(target, country));
+
+        let mut out: Vec<Country> = candidates.into_iter().take(count).cloned().collect();
+        out.shuffle(rng);
+        out
+    }
+}
+
+//distractors with codes that look alike, the same first letter first
+pub fn get_code_distractors<R: Rng>(countries: &[Country], target: &Country, count: usize, rng: &mut R) -> Vec<Country> {
+    let first: Option<char> = target.code.chars().next();
+    let (similar, other): (Vec<&Country>, Vec<&Country>) = countries
+        .iter()
+        .filter(|country| country.code != target.code)
+        .partition(|country| country.code.chars().next() == first);
+
+    let mut out: Vec<Country> = similar
+        .into_iter()
+        .choose_multiple(rng, count)
+        .into_iter()
+        .cloned()
+        .collect();
+    let rest: usize = count - out.len();
+    out.extend(other.into_iter().choose_multiple(rng, rest).into_iter().cloned());
+    out.shuffle(rng);
+    out
+}
+
+//lowercase without diacritics, spaces and punctuation
+pub fn normalize_answer(text: &str) -> String {
+    let lower: String = text.trim().to_lowercase();
+    let lower: &str = lower.strip_prefix("the ").unwrap_or(&lower);
+    let mut out: String = String::with_capacity(lower.len());
+    for ch in lower.chars() {
+        match fold_diacritic(ch) {
+            Some(folded) => out.push_str(folded),
+            None if ch.is_alphanumeric() => out.push(ch),
+            None => (),
+        }
+    }
+    out
+}
+
+fn fold_diacritic(ch: char) -> Option<&'static str> {
+    let folded: &str = match ch {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'ĵ' => "j",
+        'ķ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' => "s",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ß' => "ss",
+        'æ' => "ae",
+        'œ' => "oe",
+        //russian
+        'ё' => "е",
+        'й' => "и",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+//"Lobamba, Mbabane" and "英国 (联合王国)" also accept every part
+fn answer_variants(expected: &str) -> Vec<String> {
+    let mut out: Vec<String> = vec![expected.to_string()];
+    if let Some(open) = expected.find(['(', '（']) {
+        let inner: &str = &expected[open..];
+        out.push(expected[..open].to_string());
+        out.push(inner.trim_matches(['(', ')', '（', '）']).to_string());
+    }
+    if expected.contains([',', '，']) {
+        out.extend(expected.split([',', '，']).map(str::to_string));
+    }
+    out
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut cur: Vec<usize> = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost: usize = if ca == cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+//allowed typos, one chinese character carries more than one latin letter
+fn typo_budget(answer: &[char]) -> usize {
+    let cjk: bool = answer.iter().any(|&ch| ch >= '\u{2e80}');
+    match (cjk, answer.len()) {
+        (true, 0..=3) => 0,
+        (true, _) => 1,
+        (false, 0..=3) => 0,
+        (false, 4..=7) => 1,
+        (false, _) => 2,
+    }
+}
+
+pub fn matches_answer(input: &str, expected: &str, alternates: &[String]) -> bool {
+    let input: Vec<char> = normalize_answer(input).chars().collect();
+    if input.is_empty() {
+        return false;
+    }
+    answer_variants(expected)
+        .iter()
+        .chain(alternates.iter())
+        .map(|answer| normalize_answer(answer).chars().collect::<Vec<char>>())
+        .filter(|answer| !answer.is_empty())
+        .any(|answer| edit_distance(&input, &answer) <= typo_budget(&answer))
+}
+
+pub fn get_bad_answers<R: Rng>(excluded: usize, count: usize, num: usize, rng: &mut R) -> Vec<bool> {
+    let mut vec: Vec<bool> = vec![true; count];
+    let available_indices: Vec<usize> = (0..count)
+    .filter(|&i| i != excluded)
+    .collect();
+
+    let indices_to_change: Vec<usize> = available_indices
+    .iter()
+    .choose_multiple(rng, num)
+    .into_iter()
+    .cloned()
+    .collect();
+
+    for &idx in &indices_to_change {
+        vec[idx] = false;
+    }
+    vec
+}
+
+//wrong answers removed by the 50/50 help, same share as BAD_ANSWER of ANSWER_NUM
+pub fn bad_answer_num(count: usize) -> usize {
+    count * BAD_ANSWER / ANSWER_NUM
+}
+
+pub fn get_rand_universal<R: Rng>(count: usize, rng: &mut R) -> usize {
+    rng.random_range(0..count)
+}
+
+pub fn create_mode_list(input_config: &[bool]) -> Vec<GameMode> {
+    use GameMode::*;
+    const MODE: [GameMode; MODE_NUM] = [Flags, Capitals, Fandc, ReverseFlags, ReverseCapitals, Continents, Codes, Typing, Currencies, Neighbours, Compare];
+
+    let mut out = Vec::new();
+
+    for i in 0..MODE.len() {
+        if input_config[i] {
+            out.push(MODE[i].clone());
+        }
+    }
+    out
+}
+
+//ISO 3166 alpha-2, skips regions like `gb-eng`
+#[inline(always)]
+fn has_iso_code(country: &Country) -> bool {
+    country.code.len() == 2
+}
+
+//country can be asked in the mode
+fn fits_mode(mode: &GameMode, country: &Country) -> bool {
+    use GameMode::*;
+    match mode {
+        Capitals | Fandc | ReverseCapitals | Typing => country.capital.is_some(),
+        Codes => has_iso_code(country),
+        Continents => country.continent.is_some(),
+        Currencies => country.currency.is_some(),
+        //islands have no neighbours
+        Neighbours => !country.borders.is_empty(),
+        Compare => country.population.is_some() && country.area.is_some(),
+        Flags | ReverseFlags => true,
+    }
+}
+
+//value compared in the higher or lower questions
+fn measure(ask: Ask, country: &Country) -> Option<f64> {
+    match ask {
+        Ask::LargerPopulation => country.population.map(|population| population as f64),
+        Ask::LargerArea => country.area,
+        _ => None,
+    }
+}
+
+//larger country of the pair and the smaller one as the only wrong option, equal values are never paired
+fn compare_pair<R: Rng>(pool: Vec<Country>, target: &Country, distractors: &Distractors, rng: &mut R) -> (Country, Ask, Vec<Country>) {
+    let ask: Ask = match get_rand_universal(2, rng) == 1 {
+        true => Ask::LargerPopulation,
+        false => Ask::LargerArea,
+    };
+    let pool: Vec<Country> = pool
+        .into_iter()
+        .filter(|country| measure(ask, country) != measure(ask, target))
+        .collect();
+    let other: Country = distractors.pick(&pool, target, 1, rng).remove(0);
+    match measure(ask, &other) > measure(ask, target) {
+        true => (other, ask, vec![target.clone()]),
+        false => (target.clone(), ask, vec![other]),
+    }
+}
+
+//the pool also has to give a right and a wrong option for the target
+fn can_ask(mode: &GameMode, countries: &[Country], country: &Country) -> bool {
+    match mode {
+        GameMode::Neighbours => {
+            let mut others = countries
+                .iter()
+                .filter(|other| fits_mode(mode, other) && other.code != country.code);
+            fits_mode(mode, country)
+                && others.clone().any(|other| country.borders.contains(&other.code))
+                && others.any(|other| !country.borders.contains(&other.code))
+        }
+        GameMode::Compare => {
+            fits_mode(mode, country) && countries.iter().any(|other| fits_mode(mode, other)
+                && other.population != country.population && other.area != country.area)
+        }
+        _ => fits_mode(mode, country),
+    }
+}
+
+//at least one mode has a country to ask
+pub fn can_play(countries: &[Country], modes: &[GameMode]) -> bool {
+    modes.iter().any(|mode| countries.iter().any(|country| can_ask(mode, countries, country)))
+}
+
+//right option and the pool of wrong ones, the odd one out needs enough neighbours for the other options
+fn split_neighbours<R: Rng>(pool: Vec<Country>, target: &Country, count: usize, rng: &mut R) -> (Country, Ask, Vec<Country>) {
+    let (near, far): (Vec<Country>, Vec<Country>) = pool
+        .into_iter()
+        .filter(|country| country.code != target.code)
+        .partition(|country| target.borders.contains(&country.code));
+    let (ask, right, wrong): (Ask, Vec<Country>, Vec<Country>) =
+        match near.len() + 1 >= count && get_rand_universal(2, rng) == 1 {
+            true => (Ask::NotBorders, far, near),
+            false => (Ask::Borders, near, far),
+        };
+    let answer: Country = right[get_rand_universal(right.len(), rng)].clone();
+    (answer, ask, wrong)
+}
+
+#[inline(always)]
+fn currency_code(country: &Country) -> &str {
+    country.currency.as_ref().map_or("", |currency| currency.code.as_str())
+}
+
+#[inline(always)]
+fn currency_or_empty(country: &Country) -> String {
+    country.currency.as_ref().map(|currency| currency.name.clone()).unwrap_or_default()
+}
+
+//one random country of every key but the one of the target, so a shared
+//currency like the euro or a shared capital like London never gives two right options
+fn unique_pool<R: Rng>(pool: &[Country], target: &Country, key: fn(&Country) -> &str, rng: &mut R) -> Vec<Country> {
+    let mut shuffled: Vec<&Country> = pool.iter().collect();
+    shuffled.shuffle(rng);
+    let mut seen: Vec<&str> = vec![key(target)];
+    let mut out: Vec<Country> = Vec::new();
+    for country in shuffled {
+        if !seen.contains(&key(country)) {
+            seen.push(key(country));
+            out.push(country.clone());
+        }
+    }
+    out
+}
+
+#[inline(always)]
+fn capital_or_empty(country: &Country) -> String {
+    country.capital.clone().unwrap_or_default()
+}
+
+#[inline(always)]
+fn capital_str(country: &Country) -> &str {
+    country.capital.as_deref().unwrap_or_default()
+}
+
+//localized continent names, the right one at the answer index
+fn continent_options<R: Rng>(target: &Country, answer: usize, count: usize, tr: &ContinentsTranslation, rng: &mut R
+) -> Vec<String> {
+    let right: Continent = target.continent.clone().unwrap_or(Continent::Other);
+    //Other is too easy to rule out, so it is only an option when it is right
+    let mut continents: Vec<Continent> = CONTINENTS
+        .iter()
+        .filter(|continent| **continent != right && **continent != Continent::Other)
+        .cloned()
+        .choose_multiple(rng, count - 1);
+    continents.insert(answer.min(continents.len()), right);
+    continents.iter().map(|continent| continent.ret_continent_name(tr)).collect()
+}
+
+pub fn make_question<R: Rng>(countries: &[Country], deck: &mut Deck, mode: &[GameMode], count: usize,
+    distractors: &Distractors, tr: &ContinentsTranslation, rng: &mut R
+) -> Option<Question> {
+    use GameMode::*;
+    let askable = |deck: &Deck, mode: &GameMode| deck.holds(|card| can_ask(mode, countries, &countries[card]));
+    //only modes with a card left are played, a new round starts when none is left for any mode
+    let mut modes: Vec<&GameMode> = mode.iter().filter(|mode| askable(deck, mode)).collect();
+    if modes.is_empty() {
+        deck.shuffle(rng);
+        modes = mode.iter().filter(|mode| askable(deck, mode)).collect();
+    }
+    if modes.is_empty() {
+        return None;
+    }
+    let used_mode: GameMode = modes[get_rand_universal(modes.len(), rng)].clone();
+    let target: usize = deck.deal(rng, |card| can_ask(&used_mode, countries, &countries[card]))?;
+    let pool: Vec<Country> = countries
+        .iter()
+        .filter(|country| fits_mode(&used_mode, country))
+        .cloned()
+        .collect();
+    let (right, ask, pool): (Country, Ask, Vec<Country>) = match used_mode {
+        Neighbours => split_neighbours(pool, &countries[target], count, rng),
+        Compare => compare_pair(pool, &countries[target], distractors, rng),
+        _ => (countries[target].clone(), Ask::Answer, pool),
+    };
+    let mut used_countries: Vec<Country> = match used_mode {
+        Codes => get_code_distractors(&pool, &countries[target], count - 1, rng),
+        Typing => Vec::new(),
+        //two options whatever the number of answers
+        Compare => pool,
+        Currencies => {
+            let pool: Vec<Country> = unique_pool(&pool, &countries[target], currency_code, rng);
+            distractors.pick(&pool, &countries[target], count - 1, rng)
+        }
+        Capitals | Fandc | ReverseCapitals => {
+            let pool: Vec<Country> = unique_pool(&pool, &countries[target], capital_str, rng);
+            distractors.pick(&pool, &countries[target], count - 1, rng)
+        }
+        _ => distractors.pick(&pool, &countries[target], count - 1, rng),
+    };
+    //small pools give less options, continents do not depend on the pool
+    let answer: usize = match used_mode {
+        Continents => get_rand_universal(count.min(CONTINENTS.len() - 1), rng),
+        Typing => 0,
+        _ => get_rand_universal(used_countries.len() + 1, rng),
+    };
+    //a comparison is about the larger country, not the dealt card
+    let target: Country = match used_mode {
+        Compare => right.clone(),
+        _ => countries[target].clone(),
+    };
+    used_countries.insert(answer.min(used_countries.len()), right);
+
+    let mut accepted: Vec<String> = Vec::new();
+    //answers are shown as flags, the names are kept for the result
+    let mut flag_answers: bool = matches!(used_mode, ReverseFlags | Compare);
+    let (prompt, options): (Prompt, Vec<String>) = match used_mode {
+        Flags => (
+            Prompt::Flag(target.flag_4x3.clone()),
+            used_countries.iter().map(|c| c.name.clone()).collect()
+        ),
+        Capitals => (
+            Prompt::Text(target.name.clone()),
+            used_countries.iter().map(capital_or_empty).collect()
+        ),
+        //flag -> capital or capital -> flag
+        Fandc => match get_rand_universal(2, rng) == 1 {
+            true => {
+                flag_answers = true;
+                (Prompt::Text(capital_or_empty(&target)), used_countries.iter().map(|c| c.name.clone()).collect())
+            }
+            false => (
+                Prompt::Flag(target.flag_4x3.clone()),
+                used_countries.iter().map(capital_or_empty).collect()
+            ),
+        },
+        ReverseFlags => (
+            Prompt::Text(target.name.clone()),
+            used_countries.iter().map(|c| c.name.clone()).collect()
+        ),
+        ReverseCapitals => (
+            Prompt::Text(capital_or_empty(&target)),
+            used_countries.iter().map(|c| c.name.clone()).collect()
+        ),
+        //flag or name -> continent
+        Continents => (
+            match get_rand_universal(2, rng) == 1 {
+                true => Prompt::Text(target.name.clone()),
+                false => Prompt::Flag(target.flag_4x3.clone()),
+            },
+            continent_options(&target, answer, count, tr, rng)
+        ),
+        //code -> country name or country name -> code
+        Codes => match get_rand_universal(2, rng) == 1 {
+            true => (
+                Prompt::Text(target.name.clone()),
+                used_countries.iter().map(|c| c.code.to_uppercase()).collect()
+            ),
+            false => (
+                Prompt::Text(target.code.to_uppercase()),
+                used_countries.iter().map(|c| c.name.clone()).collect()
+            ),
+        },
+        //country name -> currency or currency -> country name
+        Currencies => match get_rand_universal(2, rng) == 1 {
+            true => (
+                Prompt::Text(target.name.clone()),
+                used_countries.iter().map(currency_or_empty).collect()
+            ),
+            false => (
+                Prompt::Text(currency_or_empty(&target)),
+                used_countries.iter().map(|c| c.name.clone()).collect()
+            ),
+        },
+        //country name -> a neighbour or the only country that is not one
+        Neighbours => (
+            Prompt::Text(target.name.clone()),
+            used_countries.iter().map(|c| c.name.clone()).collect()
+        ),
+        Compare => (
+            Prompt::Versus,
+            used_countries.iter().map(|c| c.name.clone()).collect()
+        ),
+        //flag -> typed country name or country name -> typed capital
+        Typing => match get_rand_universal(2, rng) == 1 {
+            true => {
+                accepted = target.alt_capitals.clone();
+                (Prompt::Text(target.name.clone()), vec![capital_or_empty(&target)])
+            }
+            false => {
+                accepted = target.alt_names.clone();
+                (Prompt::Flag(target.flag_4x3.clone()), vec![target.name.clone()])
+            }
+        },
+    };
+    let option_flags: Option<Vec<String>> = match flag_answers {
+        true => Some(used_countries.iter().map(|c| c.flag_4x3.clone()).collect()),
+        false => None,
+    };
+
+    let typed: bool = used_mode == Typing;
+
+    Some(Question { mode: used_mode, prompt, ask, target, options, option_flags, answer, typed, accepted })
+}
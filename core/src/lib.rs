@@ -0,0 +1,11 @@
+//! Game logic of the Geographical game without any UI dependency.
+
+pub mod consts;
+pub mod country;
+pub mod daily;
+pub mod difficulty;
+pub mod flagcolor;
+pub mod gamelogic;
+pub mod score;
+pub mod session;
+pub mod timer;
@@ -0,0 +1,53 @@
+use crate::consts::score::*;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Score {
+    pub correct: u32,
+    pub wrong: u32,
+    pub time_out: u32,
+    pub help: u32,
+    //right answers in a row, the current one and the longest of the game
+    pub streak: u32,
+    pub best_streak: u32,
+    points: u32,
+    help_in_question: bool,
+}
+
+impl Score {
+    pub fn correct(&mut self, ticks_left: u32) {
+        self.correct += 1;
+        self.streak += 1;
+        self.best_streak = self.best_streak.max(self.streak);
+        self.points += CORRECT + ticks_left * TICK;
+        self.help_in_question = false;
+    }
+
+    pub fn wrong(&mut self) {
+        self.wrong += 1;
+        self.streak = 0;
+        self.help_in_question = false;
+    }
+
+    pub fn time_out(&mut self) {
+        self.time_out += 1;
+        self.streak = 0;
+        self.help_in_question = false;
+    }
+
+    //bonus of the higher or lower questions for every earlier right answer in a row
+    pub fn streak_bonus(&mut self) {
+        self.points += self.streak.saturating_sub(1).min(STREAK_MAX) * STREAK;
+    }
+
+    //penalty is counted once per question
+    pub fn help(&mut self) {
+        if !self.help_in_question {
+            self.help += 1;
+            self.help_in_question = true;
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.points.saturating_sub(self.help * HELP_PENALTY)
+    }
+}
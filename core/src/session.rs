@@ -0,0 +1,387 @@
+use rand::rngs::StdRng;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::consts::{ANSWER_NUM, ANSWER_NUMS, TIMER_TICKS};
+use crate::country::{self, ContinentsTranslation, Country, IsoFilter, Regions};
+use crate::difficulty::Rules;
+use crate::flagcolor::FlagColors;
+use crate::gamelogic::{self, Deck, Distractors, GameMode, Question, Similarity};
+use crate::score::Score;
+use crate::timer::Timer;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum State {
+    Idle,
+    Asking,
+    Answered,
+    Finished,
+}
+
+//how the game ends, the session length means
+//questions for Questions, seconds of the budget for TimeAttack and nothing for SuddenDeath
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Ending {
+    #[default]
+    Questions,
+    SuddenDeath,
+    TimeAttack,
+}
+
+impl Ending {
+    //the help would make the streak and the budget games too easy
+    pub fn allows_help(&self) -> bool {
+        *self == Ending::Questions
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Answer {
+    Picked(usize),
+    Typed(String),
+    TimeOut,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Outcome {
+    pub correct: bool,
+    pub selected: Option<usize>,
+    pub answer: usize,
+    //time from the question to the answer
+    pub time: Duration,
+}
+
+//one game from the first question to the end screen
+pub struct Session {
+    countries: Vec<Country>,
+    tr: ContinentsTranslation,
+    pool: Vec<Country>,
+    modes: Vec<GameMode>,
+    deck: Deck,
+    length: u32,
+    answers: usize,
+    help_allowed: bool,
+    territories: bool,
+    iso: IsoFilter,
+    distractors: Distractors,
+    number: u32,
+    question: Option<Question>,
+    score: Score,
+    timer: Timer,
+    ending: Ending,
+    //time budget of the whole game in time attack
+    clock: Timer,
+    paused: bool,
+    times: Vec<Duration>,
+    state: State,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Session {
+    pub fn new(countries: Vec<Country>, tr: ContinentsTranslation) -> Self {
+        let seed: u64 = gamelogic::new_seed();
+        Session {
+            countries,
+            tr,
+            pool: Vec::new(),
+            modes: Vec::new(),
+            deck: Deck::default(),
+            length: 0,
+            answers: ANSWER_NUM,
+            help_allowed: true,
+            territories: true,
+            iso: IsoFilter::default(),
+            distractors: Distractors::default(),
+            number: 0,
+            question: None,
+            score: Score::default(),
+            timer: Timer::new(TIMER_TICKS),
+            ending: Ending::Questions,
+            clock: Timer::new(0),
+            paused: false,
+            times: Vec::new(),
+            state: State::Idle,
+            seed,
+            rng: gamelogic::seeded_rng(seed),
+        }
+    }
+
+    pub fn set_filter(&mut self, regions: &Regions, modes: Vec<GameMode>) {
+        //the difficulty may leave only the sovereign states
+        let iso: IsoFilter = match self.territories {
+            true => self.iso,
+            false => IsoFilter { territories: false, organisations: false },
+        };
+        self.pool = country::filter_by_regions(&self.countries, regions, iso);
+        self.modes = modes;
+        self.deck = Deck::new(self.pool.len(), &mut self.rng);
+    }
+
+    //unsupported numbers fall back to the default
+    pub fn set_answers(&mut self, answers: usize) {
+        self.answers = match ANSWER_NUMS.contains(&answers) {
+            true => answers,
+            false => ANSWER_NUM,
+        };
+    }
+
+    pub fn set_similarity(&mut self, similarity: Similarity) {
+        self.distractors.similarity = similarity;
+    }
+
+    //call before set_filter
+    pub fn set_iso_filter(&mut self, iso: IsoFilter) {
+        self.iso = iso;
+    }
+
+    //call before set_filter, territories change the pool
+    pub fn set_rules(&mut self, rules: &Rules) {
+        self.set_answers(rules.answers);
+        self.set_similarity(rules.similarity);
+        self.timer = Timer::new(rules.ticks);
+        self.help_allowed = rules.help;
+        self.territories = rules.territories;
+    }
+
+    //call before start
+    pub fn set_ending(&mut self, ending: Ending) {
+        self.ending = ending;
+    }
+
+    //flag colours for the similar flags distractors
+    pub fn set_flag_colors(&mut self, colors: FlagColors) {
+        self.distractors.colors = colors;
+    }
+
+    //the same seed, filter and length always give the same questions
+    pub fn start(&mut self, length: u32, seed: Option<u64>) {
+        self.seed = seed.unwrap_or_else(gamelogic::new_seed);
+        self.rng = gamelogic::seeded_rng(self.seed);
+        self.deck = Deck::new(self.pool.len(), &mut self.rng);
+        self.length = length;
+        self.number = 0;
+        self.question = None;
+        self.score = Score::default();
+        self.times.clear();
+        self.timer.reset();
+        self.clock = Timer::new(match self.ending {
+            Ending::TimeAttack => length,
+            _ => 0,
+        });
+        self.paused = false;
+        self.state = State::Idle;
+    }
+
+    //returns None when the game is over
+    pub fn next_question(&mut self) -> Option<&Question> {
+        if self.over() || self.modes.is_empty() {
+            return self.finish();
+        }
+        match gamelogic::make_question(&self.pool, &mut self.deck, &self.modes, self.answers, &self.distractors, &self.tr, &mut self.rng) {
+            Some(question) => {
+                self.number += 1;
+                self.question = Some(question);
+                self.timer.reset();
+                if self.paused {
+                    self.timer.pause();
+                }
+                self.state = State::Asking;
+                self.question.as_ref()
+            }
+            None => self.finish(),
+        }
+    }
+
+    fn over(&self) -> bool {
+        match self.ending {
+            Ending::Questions => self.number >= self.length,
+            Ending::SuddenDeath => self.score.wrong + self.score.time_out > 0,
+            Ending::TimeAttack => self.clock.expired(),
+        }
+    }
+
+    fn finish(&mut self) -> Option<&Question> {
+        self.question = None;
+        self.state = State::Finished;
+        None
+    }
+
+    pub fn answer(&mut self, answer: Answer) -> Option<Outcome> {
+        if self.state != State::Asking {
+            return None;
+        }
+        let question: &Question = self.question.as_ref()?;
+        //the question timer does not limit the answers of time attack
+        let time: Duration = match self.ending {
+            Ending::TimeAttack => self.timer.spent(),
+            _ => self.timer.elapsed(),
+        };
+        //time attack has no question time out, only the game clock runs out
+        let clock_out: bool = self.ending == Ending::TimeAttack && answer == Answer::TimeOut;
+        let outcome: Outcome = match answer {
+            Answer::Picked(index) => Outcome {
+                correct: question.check(index),
+                selected: Some(index),
+                answer: question.answer,
+                time,
+            },
+            Answer::Typed(ref text) => Outcome {
+                correct: question.check_text(text),
+                selected: None,
+                answer: question.answer,
+                time,
+            },
+            Answer::TimeOut => Outcome {
+                correct: false,
+                selected: None,
+                answer: question.answer,
+                time,
+            },
+        };
+        if !clock_out {
+            self.times.push(time);
+        }
+        match (answer, outcome.correct) {
+            (Answer::TimeOut, _) if clock_out => {}
+            (Answer::TimeOut, _) => self.score.time_out(),
+            (_, true) if self.ending == Ending::Questions => {
+                self.score.correct(self.timer.left());
+                if question.mode == GameMode::Compare {
+                    self.score.streak_bonus();
+                }
+            }
+            (_, true) => self.score.correct(0),
+            (_, false) => self.score.wrong(),
+        }
+        self.state = State::Answered;
+        Some(outcome)
+    }
+
+    //runs the question timer, returns true when the time is just over
+    //in time attack only the game budget runs out
+    pub fn tick(&mut self, time: Duration) -> bool {
+        if self.state != State::Asking {
+            return false;
+        }
+        let expired: bool = self.timer.advance(time);
+        match self.ending {
+            Ending::TimeAttack => self.clock.advance(time),
+            _ => expired,
+        }
+    }
+
+    //the pause lasts over the next questions until resume
+    pub fn pause(&mut self) {
+        self.paused = true;
+        self.timer.pause();
+        self.clock.pause();
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+        self.timer.resume();
+        self.clock.resume();
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    //whole seconds left for the question, for the game in time attack
+    pub fn time_left(&self) -> u32 {
+        match self.ending {
+            Ending::TimeAttack => self.clock.left(),
+            _ => self.timer.left(),
+        }
+    }
+
+    //response time of every answered question
+    pub fn response_times(&self) -> &[Duration] {
+        &self.times
+    }
+
+    pub fn average_response(&self) -> Option<Duration> {
+        let count: u32 = self.times.len() as u32;
+        (count > 0).then(|| self.times.iter().sum::<Duration>() / count)
+    }
+
+    //50/50 help, returns the answers that stay enabled
+    //does not use the session rng, so the question sequence stays the same
+    pub fn help(&mut self) -> Vec<bool> {
+        match &self.question {
+            Some(question) if self.help_available() => {
+                self.score.help();
+                let count: usize = question.options.len();
+                gamelogic::get_bad_answers(question.answer, count, gamelogic::bad_answer_num(count), &mut rand::rng())
+            }
+            Some(question) => vec![true; question.options.len()],
+            None => vec![true; self.answers],
+        }
+    }
+
+    //neighbours are looked up in all the countries, not only in the pool of the game
+    pub fn neighbours(&self, country: &Country) -> Vec<&Country> {
+        country.borders
+            .iter()
+            .filter_map(|code| country::find_by_code(&self.countries, code))
+            .collect()
+    }
+
+    pub fn question(&self) -> Option<&Question> {
+        self.question.as_ref()
+    }
+
+    //the regions and modes can give a question
+    pub fn can_start(&self) -> bool {
+        gamelogic::can_play(&self.pool, &self.modes)
+    }
+
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    pub fn length(&self) -> u32 {
+        self.length
+    }
+
+    pub fn answers(&self) -> usize {
+        self.answers
+    }
+
+    //help for the current question
+    pub fn help_available(&self) -> bool {
+        match &self.question {
+            Some(question) => self.state == State::Asking && self.help_allowed && question.allows_help(),
+            None => false,
+        }
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    //points for the questions game, right answers for sudden death and time attack
+    pub fn total(&self) -> u32 {
+        match self.ending {
+            Ending::Questions => self.score.total(),
+            Ending::SuddenDeath | Ending::TimeAttack => self.score.correct,
+        }
+    }
+
+    pub fn ending(&self) -> Ending {
+        self.ending
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn timer(&self) -> &Timer {
+        &self.timer
+    }
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+//question countdown, one tick is one second
+//the time is given by the caller, so the timer does not depend on a clock
+#[derive(Debug, Clone, Copy)]
+pub struct Timer {
+    limit: u32,
+    elapsed: Duration,
+    //elapsed time past the limit too
+    spent: Duration,
+    paused: bool,
+}
+
+impl Timer {
+    pub fn new(limit: u32) -> Self {
+        Timer { limit, elapsed: Duration::ZERO, spent: Duration::ZERO, paused: false }
+    }
+
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+        self.spent = Duration::ZERO;
+        self.paused = false;
+    }
+
+    //returns true when the time is over, paused time is not counted
+    pub fn advance(&mut self, time: Duration) -> bool {
+        if !self.paused {
+            self.spent += time;
+            self.elapsed = self.spent.min(Duration::from_secs(self.limit as u64));
+        }
+        self.expired()
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    //whole ticks left
+    pub fn left(&self) -> u32 {
+        self.limit.saturating_sub(self.elapsed.as_secs() as u32)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn spent(&self) -> Duration {
+        self.spent
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn expired(&self) -> bool {
+        self.left() == 0
+    }
+}
@@ -0,0 +1,781 @@
+use geo_game_core::country::{self as geo_country, Continent, ContinentsTranslation, Country, Currency, DrivingSide, IsoFilter, Regions, Subregion, CONTINENTS};
+use geo_game_core::daily::Daily;
+use geo_game_core::difficulty::{Difficulty, Rules};
+use geo_game_core::flagcolor::{ColorShare, FlagColors, Rgb};
+use geo_game_core::gamelogic::{self, Ask, Deck, Distractors, GameMode, Prompt, Similarity};
+use geo_game_core::session::{Answer, Ending, Session, State};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+//flags are rendered the same way as by the example writing the colours file
+#[path = "../examples/flag_colors/render.rs"]
+mod render;
+
+fn country(code: &str, continent: Continent, capital: Option<&str>) -> Country {
+    Country {
+        alt_capitals: Vec::new(),
+        alt_names: Vec::new(),
+        area: None,
+        borders: Vec::new(),
+        calling_code: None,
+        capital: capital.map(str::to_string),
+        code: code.to_string(),
+        continent: Some(continent),
+        currency: None,
+        driving_side: None,
+        flag_4x3: format!("{code}.svg"),
+        iso: true,
+        languages: Vec::new(),
+        name: code.to_uppercase(),
+        population: None,
+        subregion: None,
+    }
+}
+
+fn countries() -> Vec<Country> {
+    use Continent::*;
+    vec![
+        country("fr", Europe, Some("Paris")),
+        country("de", Europe, Some("Berlin")),
+        country("it", Europe, Some("Rome")),
+        country("es", Europe, Some("Madrid")),
+        country("pt", Europe, Some("Lisbon")),
+        country("jp", Asia, Some("Tokyo")),
+        country("cn", Asia, Some("Beijing")),
+        country("va", Europe, None),
+        country("aq", Other, None),
+    ]
+}
+
+fn translation() -> ContinentsTranslation {
+    ContinentsTranslation {
+        eur: "Europe".to_string(),
+        asi: "Asia".to_string(),
+        afr: "Africa".to_string(),
+        nam: "North America".to_string(),
+        sam: "South America".to_string(),
+        oce: "Oceania".to_string(),
+        other: "Other".to_string(),
+    }
+}
+
+fn europe_session(modes: Vec<GameMode>, length: u32) -> Session {
+    let mut session = Session::new(countries(), translation());
+    session.set_filter(&Regions::continents(&[Continent::Europe]), modes);
+    session.start(length, None);
+    session
+}
+
+#[test]
+fn deck_deals_every_card_once() {
+    let mut rng = gamelogic::seeded_rng(7);
+    let mut deck = Deck::new(5, &mut rng);
+    let mut dealt: Vec<usize> = (0..5).map(|_| deck.deal(&mut rng, |_| true).unwrap()).collect();
+    dealt.sort();
+    assert_eq!(dealt, vec![0, 1, 2, 3, 4]);
+    assert!(deck.deal(&mut rng, |_| true).is_some());
+}
+
+#[test]
+fn deck_keeps_cards_that_do_not_fit() {
+    let mut rng = gamelogic::seeded_rng(7);
+    let mut deck = Deck::new(5, &mut rng);
+    let mut even: Vec<usize> = (0..3).map(|_| deck.deal(&mut rng, |card| card % 2 == 0).unwrap()).collect();
+    even.sort();
+    assert_eq!(even, vec![0, 2, 4]);
+    assert!(deck.deal(&mut rng, |card| card % 2 == 0).is_none());
+    let mut odd: Vec<usize> = (0..2).map(|_| deck.deal(&mut rng, |_| true).unwrap()).collect();
+    odd.sort();
+    assert_eq!(odd, vec![1, 3]);
+}
+
+#[test]
+fn modes_without_candidates_are_skipped() {
+    //no country has borders, so neighbours can not be asked
+    let mut session = europe_session(vec![GameMode::Neighbours, GameMode::Flags], 8);
+    let mut asked: u32 = 0;
+    while let Some(question) = session.next_question() {
+        assert!(matches!(question.prompt, Prompt::Flag(_)));
+        asked += 1;
+        session.answer(Answer::TimeOut);
+    }
+    assert_eq!(asked, 8);
+}
+
+#[test]
+fn selection_without_questions_can_not_start() {
+    let mut session = europe_session(vec![GameMode::Neighbours], 5);
+    assert!(!session.can_start());
+    assert!(session.next_question().is_none());
+    assert_eq!(session.number(), 0);
+
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Neighbours, GameMode::Flags]);
+    assert!(session.can_start());
+}
+
+#[test]
+fn session_asks_the_requested_number_of_questions() {
+    let mut session = europe_session(vec![GameMode::Flags], 5);
+    let mut targets: Vec<String> = Vec::new();
+
+    while let Some(question) = session.next_question() {
+        assert_eq!(question.options.len(), 4);
+        assert_eq!(question.options[question.answer], question.target.name);
+        assert_eq!(question.prompt, Prompt::Flag(question.target.flag_4x3.clone()));
+        targets.push(question.target.code.clone());
+        session.answer(Answer::TimeOut);
+    }
+
+    targets.sort();
+    assert_eq!(targets.len(), 5);
+    targets.dedup();
+    assert_eq!(targets.len(), 5);
+    assert_eq!(session.state(), State::Finished);
+    assert_eq!(session.score().time_out, 5);
+}
+
+#[test]
+fn capitals_are_checked_and_scored() {
+    let mut session = europe_session(vec![GameMode::Capitals], 2);
+
+    let question = session.next_question().unwrap().clone();
+    assert_eq!(question.options[question.answer], question.target.capital.clone().unwrap());
+    assert!(!session.tick(Duration::from_secs(50)));
+    assert_eq!(session.time_left(), 10);
+    let outcome = session.answer(Answer::Picked(question.answer)).unwrap();
+    assert!(outcome.correct);
+    assert!(session.answer(Answer::Picked(question.answer)).is_none());
+
+    let question = session.next_question().unwrap().clone();
+    session.help();
+    session.help();
+    let wrong: usize = (question.answer + 1) % question.options.len();
+    assert!(!session.answer(Answer::Picked(wrong)).unwrap().correct);
+
+    let score = session.score();
+    assert_eq!((score.correct, score.wrong, score.help), (1, 1, 1));
+    assert_eq!(score.total(), 100 + 10 * 5 - 50);
+}
+
+fn play(session: &mut Session, seed: u64) -> Vec<(String, Vec<String>)> {
+    session.start(5, Some(seed));
+    let mut out = Vec::new();
+    while let Some(question) = session.next_question() {
+        out.push((question.target.code.clone(), question.options.clone()));
+        let answer: usize = question.answer;
+        session.help();
+        session.answer(Answer::Picked(answer));
+    }
+    out
+}
+
+#[test]
+fn same_seed_gives_same_questions() {
+    let modes = vec![GameMode::Flags, GameMode::Capitals, GameMode::Fandc];
+    let mut first = europe_session(modes.clone(), 0);
+    let mut second = europe_session(modes, 0);
+
+    let questions = play(&mut first, 2024);
+    assert_eq!(questions, play(&mut second, 2024));
+    assert_eq!(questions, play(&mut first, 2024));
+    assert_eq!(first.seed(), 2024);
+    assert_ne!(questions, play(&mut second, 2025));
+}
+
+#[test]
+fn daily_challenge_depends_only_on_date() {
+    let today = Daily::from_date(2026, 10, 18);
+    assert_eq!(today, Daily::from_date(2026, 10, 18));
+    assert_ne!(today.seed, Daily::from_date(2026, 10, 19).seed);
+    assert!(!today.modes.is_empty());
+    //the modes of a date stay the same when new modes are added
+    assert_eq!(Daily::from_date(2025, 1, 1).modes, vec![GameMode::Fandc]);
+    assert_eq!(today.modes, vec![GameMode::Capitals, GameMode::Fandc]);
+}
+
+#[test]
+fn reverse_flags_answer_with_flag_files() {
+    let mut session = europe_session(vec![GameMode::ReverseFlags], 1);
+    let question = session.next_question().unwrap();
+    let flags = question.option_flags.clone().unwrap();
+
+    assert_eq!(question.prompt, Prompt::Text(question.target.name.clone()));
+    assert_eq!(flags[question.answer], question.target.flag_4x3);
+}
+
+#[test]
+fn reverse_capitals_skip_countries_without_capital() {
+    let mut session = europe_session(vec![GameMode::ReverseCapitals], 12);
+
+    while let Some(question) = session.next_question() {
+        assert_ne!(question.target.code, "va");
+        assert_eq!(question.prompt, Prompt::Text(question.target.capital.clone().unwrap()));
+        assert!(!question.options.contains(&"VA".to_string()));
+        session.answer(Answer::TimeOut);
+    }
+}
+
+#[test]
+fn continents_are_localized_options() {
+    let mut session = Session::new(countries(), translation());
+    session.set_filter(&Regions::continents(&[Continent::Asia]), vec![GameMode::Continents]);
+    session.start(3, Some(1));
+
+    while let Some(question) = session.next_question() {
+        assert!(["jp", "cn"].contains(&question.target.code.as_str()));
+        assert_eq!(question.options.len(), 4);
+        assert_eq!(question.options[question.answer], "Asia");
+        assert!(!question.options.contains(&"Other".to_string()));
+        session.answer(Answer::TimeOut);
+    }
+}
+
+#[test]
+fn code_distractors_share_the_first_letter() {
+    let mut countries = countries();
+    countries.push(country("fi", Continent::Europe, Some("Helsinki")));
+    countries.push(country("fj", Continent::Oceania, Some("Suva")));
+    countries.push(country("gb-eng", Continent::Europe, Some("London")));
+    let target = countries[0].clone();
+    let mut rng = gamelogic::seeded_rng(3);
+
+    let similar = gamelogic::get_code_distractors(&countries, &target, 2, &mut rng);
+    let mut codes: Vec<&str> = similar.iter().map(|c| c.code.as_str()).collect();
+    codes.sort();
+    assert_eq!(codes, vec!["fi", "fj"]);
+    assert_eq!(gamelogic::get_code_distractors(&countries, &target, 3, &mut rng).len(), 3);
+}
+
+#[test]
+fn typed_answers_are_matched_fuzzy() {
+    use gamelogic::matches_answer;
+    let alt: Vec<String> = vec!["Ivory Coast".to_string()];
+
+    assert!(matches_answer("cote d'ivoire", "Côte d'Ivoire", &alt));
+    assert!(matches_answer("ivory cost", "Côte d'Ivoire", &alt));
+    assert!(matches_answer("Mbabane", "Lobamba, Mbabane", &[]));
+    assert!(matches_answer("the netherlands", "Netherlands", &[]));
+    assert!(matches_answer("киев", "Киев", &[]));
+    assert!(matches_answer("Кишинев", "Кишинёв", &[]));
+    assert!(matches_answer("英国", "英国 (联合王国)", &[]));
+    assert!(matches_answer("联合王国", "英国 (联合王国)", &[]));
+    assert!(!matches_answer("法国", "英国", &[]));
+    assert!(!matches_answer("Chad", "Chile", &[]));
+    assert!(!matches_answer("", "Chad", &[]));
+}
+
+#[test]
+fn typing_mode_checks_text() {
+    let mut session = europe_session(vec![GameMode::Typing], 1);
+    let question = session.next_question().unwrap().clone();
+    assert!(question.typed);
+    assert_eq!(question.options.len(), 1);
+
+    let text: String = question.options[0].to_lowercase();
+    assert!(session.answer(Answer::Typed(text)).unwrap().correct);
+}
+
+#[test]
+fn answer_count_is_configurable() {
+    let mut session = Session::new(countries(), translation());
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.set_answers(6);
+    session.start(1, Some(3));
+    assert_eq!(session.next_question().unwrap().options.len(), 6);
+
+    let enabled: Vec<bool> = session.help();
+    assert_eq!(enabled.len(), 6);
+    assert_eq!(enabled.iter().filter(|on| !**on).count(), 3);
+
+    session.set_answers(5);
+    assert_eq!(session.answers(), 4);
+}
+
+#[test]
+fn help_is_off_with_two_answers() {
+    let mut session = Session::new(countries(), translation());
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.set_answers(2);
+    session.start(1, Some(3));
+    assert_eq!(session.next_question().unwrap().options.len(), 2);
+    assert!(!session.help_available());
+
+    assert_eq!(session.help(), vec![true, true]);
+    assert_eq!(session.score().help, 0);
+}
+
+fn analyze(svg: &str) -> Vec<(String, f32)> {
+    render::analyze_svg(svg)
+        .into_iter()
+        .map(|color| (String::from(color.color), color.share))
+        .collect()
+}
+
+fn assert_thirds(colors: &[(String, f32)], expected: [&str; 3]) {
+    assert_eq!(colors.len(), 3);
+    for color in expected {
+        let share: f32 = colors.iter().find(|(rgb, _)| rgb == color).unwrap().1;
+        assert!((share - 1.0 / 3.0).abs() < 0.02, "{color} {share}");
+    }
+}
+
+#[test]
+fn tricolour_flags_are_split_in_thirds() {
+    let france: Vec<(String, f32)> = analyze(include_str!("../../assets/flags/4x3/fr.svg"));
+    assert_thirds(&france, ["#ffffff", "#000091", "#e1000f"]);
+    let italy: Vec<(String, f32)> = analyze(include_str!("../../assets/flags/4x3/it.svg"));
+    assert_thirds(&italy, ["#ffffff", "#009246", "#ce2b37"]);
+
+    assert_eq!(Rgb::parse("#FFF"), Some(Rgb { r: 255, g: 255, b: 255 }));
+    assert_eq!(Rgb::parse("#E1000f"), Some(Rgb { r: 225, g: 0, b: 15 }));
+    assert_eq!(Rgb::parse("none"), None);
+}
+
+#[test]
+fn flag_colors_file_is_up_to_date() {
+    let colors: FlagColors = serde_json::from_str(include_str!("../../data/flag_colors.json")).unwrap();
+    let flags: PathBuf = Path::new(env!("CARGO_MANIFEST_DIR")).join("../assets/flags/4x3");
+    let mut count: usize = 0;
+    for entry in fs::read_dir(flags).unwrap() {
+        let path: PathBuf = entry.unwrap().path();
+        let name: &str = path.file_name().unwrap().to_str().unwrap();
+        let mut flag: Country = country("xx", Continent::Other, None);
+        flag.flag_4x3 = name.to_string();
+        assert_eq!(colors.of(&flag), render::analyze_svg(&fs::read_to_string(&path).unwrap()), "{name}");
+        count += 1;
+    }
+    assert_eq!(colors.len(), count);
+
+    let france: Country = country("fr", Continent::Europe, Some("Paris"));
+    let italy: Country = country("it", Continent::Europe, Some("Rome"));
+    let japan: Country = country("jp", Continent::Asia, Some("Tokyo"));
+    let germany: Country = country("de", Continent::Europe, Some("Berlin"));
+    assert_eq!(colors.dominant(&japan), Rgb::parse("#fff"));
+    assert_eq!(colors.distance(&france, &france), 0.0);
+    assert!(colors.distance(&france, &italy) < colors.distance(&france, &germany));
+}
+
+#[test]
+fn similar_distractors_are_preferred() {
+    let countries: Vec<Country> = countries();
+    let target: &Country = &countries[5];
+    let mut rng = gamelogic::seeded_rng(11);
+    let mut distractors = Distractors { similarity: Similarity::Continent, ..Distractors::default() };
+    let picked: Vec<Country> = distractors.pick(&countries, target, 1, &mut rng);
+    assert_eq!(picked[0].code, "cn");
+
+    distractors.similarity = Similarity::Colours;
+    distractors.colors.insert("jp.svg", vec![ColorShare { color: Rgb { r: 255, g: 0, b: 0 }, share: 1.0 }]);
+    distractors.colors.insert("de.svg", vec![ColorShare { color: Rgb { r: 230, g: 0, b: 0 }, share: 1.0 }]);
+    distractors.colors.insert("it.svg", vec![ColorShare { color: Rgb { r: 0, g: 0, b: 255 }, share: 1.0 }]);
+    let picked: Vec<Country> = distractors.pick(&countries, target, 1, &mut rng);
+    assert_eq!(picked[0].code, "de");
+}
+
+#[test]
+fn same_looking_flags_are_not_distractors() {
+    let colors: FlagColors = serde_json::from_str(include_str!("../../data/flag_colors.json")).unwrap();
+    let mut countries: Vec<Country> = countries();
+    for code in ["re", "gp", "gf", "yt", "pm"] {
+        countries.push(country(code, Continent::Other, None));
+    }
+    let france: Country = countries[0].clone();
+    let distractors = Distractors { similarity: Similarity::Colours, colors };
+    let mut rng = gamelogic::seeded_rng(2);
+    for _ in 0..20 {
+        for picked in distractors.pick(&countries, &france, 3, &mut rng) {
+            assert!(!["re", "gp", "gf", "yt", "pm"].contains(&picked.code.as_str()), "{}", picked.code);
+        }
+    }
+}
+
+#[test]
+fn difficulty_changes_the_rules() {
+    let mut countries: Vec<Country> = countries();
+    let mut gibraltar: Country = country("gi", Continent::Europe, Some("Gibraltar"));
+    gibraltar.iso = false;
+    countries.push(gibraltar);
+
+    let custom: Rules = Rules { answers: 8, similarity: Similarity::Spelling, ..Rules::default() };
+    let easy: Rules = Difficulty::Easy.rules(&custom);
+    let mut session = Session::new(countries.clone(), translation());
+    session.set_rules(&easy);
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.start(6, Some(5));
+    let mut asked: Vec<String> = Vec::new();
+    while let Some(question) = session.next_question() {
+        asked.push(question.target.code.clone());
+        session.answer(Answer::TimeOut);
+    }
+    assert!(!asked.contains(&"gi".to_string()));
+    assert_eq!(session.timer().limit(), easy.ticks);
+
+    let hard: Rules = Difficulty::Hard.rules(&custom);
+    assert!(!hard.help);
+    session.set_rules(&hard);
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.start(1, Some(5));
+    assert_eq!(session.next_question().unwrap().options.len(), hard.answers);
+    assert!(session.help().iter().all(|on| *on));
+    session.answer(Answer::Picked(0));
+    assert_eq!(session.score().help, 0);
+
+    assert_eq!(Difficulty::Custom.rules(&custom), custom);
+}
+
+#[test]
+fn timer_pauses_and_records_response_times() {
+    let mut session = europe_session(vec![GameMode::Flags], 3);
+
+    let question = session.next_question().unwrap().clone();
+    session.tick(Duration::from_millis(1500));
+    session.pause();
+    assert!(!session.tick(Duration::from_secs(100)));
+    session.resume();
+    session.tick(Duration::from_millis(500));
+    let outcome = session.answer(Answer::Picked(question.answer)).unwrap();
+    assert_eq!(outcome.time, Duration::from_secs(2));
+
+    //the pause lasts over the next question
+    session.pause();
+    session.next_question().unwrap();
+    assert!(!session.tick(Duration::from_secs(100)));
+    session.resume();
+    assert!(session.tick(Duration::from_secs(100)));
+    session.answer(Answer::TimeOut);
+    assert!(!session.tick(Duration::from_secs(1)));
+
+    assert_eq!(session.response_times(), &[Duration::from_secs(2), Duration::from_secs(60)]);
+    assert_eq!(session.average_response(), Some(Duration::from_secs(31)));
+    assert_eq!(session.score().time_out, 1);
+}
+
+#[test]
+fn sudden_death_ends_at_the_first_mistake() {
+    let mut session = Session::new(countries(), translation());
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.set_ending(Ending::SuddenDeath);
+    session.start(0, Some(3));
+
+    //the streak runs over the pool size
+    for _ in 0..8 {
+        let answer: usize = session.next_question().unwrap().answer;
+        session.answer(Answer::Picked(answer));
+    }
+    let answer: usize = session.next_question().unwrap().answer;
+    session.answer(Answer::Picked((answer + 1) % 4));
+    assert!(session.next_question().is_none());
+    assert_eq!(session.total(), 8);
+}
+
+#[test]
+fn time_attack_ends_when_the_budget_is_spent() {
+    let mut session = Session::new(countries(), translation());
+    session.set_rules(&Rules { ticks: 30, ..Rules::default() });
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.set_ending(Ending::TimeAttack);
+    session.start(60, Some(3));
+
+    let answer: usize = session.next_question().unwrap().answer;
+    assert!(!session.tick(Duration::from_secs(50)));
+    session.answer(Answer::Picked(answer));
+    //wrong answers do not end the game
+    let answer: usize = session.next_question().unwrap().answer;
+    session.answer(Answer::Picked((answer + 1) % 4));
+    session.next_question().unwrap();
+    assert_eq!(session.time_left(), 10);
+    assert!(session.tick(Duration::from_secs(10)));
+    session.answer(Answer::TimeOut);
+    assert!(session.next_question().is_none());
+    assert_eq!(session.total(), 1);
+    assert_eq!(session.score().wrong, 1);
+    //slow answers are not cut to the question limit and the clock is not a time out
+    assert_eq!(session.response_times(), &[Duration::from_secs(50), Duration::ZERO]);
+    assert_eq!(session.score().time_out, 0);
+}
+
+#[test]
+fn iso_filter_selects_territories_and_organisations() {
+    let mut countries: Vec<Country> = countries();
+    let mut gibraltar: Country = country("gi", Continent::Europe, Some("Gibraltar"));
+    gibraltar.iso = false;
+    countries.push(gibraltar);
+    let mut union: Country = country("eu", Continent::Europe, None);
+    union.continent = None;
+    union.iso = false;
+    countries.push(union);
+
+    let codes = |iso: IsoFilter| -> Vec<String> {
+        geo_country::filter_by_regions(&countries, &Regions::continents(&[Continent::Asia]), iso)
+            .into_iter()
+            .chain(geo_country::filter_by_regions(&countries, &Regions::continents(&[Continent::Europe]), iso))
+            .map(|country| country.code)
+            .filter(|code| code == "gi" || code == "eu")
+            .collect()
+    };
+    assert!(codes(IsoFilter { territories: false, organisations: false }).is_empty());
+    assert_eq!(codes(IsoFilter::default()), vec!["gi"]);
+    //organisations are not bound to a continent
+    assert_eq!(codes(IsoFilter { territories: false, organisations: true }), vec!["eu", "eu"]);
+
+    //sovereign only rules override the filter of the player
+    let mut session = Session::new(countries.clone(), translation());
+    session.set_iso_filter(IsoFilter { territories: true, organisations: true });
+    session.set_rules(&Difficulty::Easy.rules(&Rules::default()));
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Flags]);
+    session.start(20, Some(1));
+    while let Some(question) = session.next_question() {
+        assert!(question.target.iso);
+        session.answer(Answer::TimeOut);
+    }
+}
+
+#[test]
+fn other_region_can_be_asked() {
+    let mut countries: Vec<Country> = countries();
+    let mut bouvet: Country = country("bv", Continent::Other, None);
+    bouvet.continent = None;
+    countries.push(bouvet);
+
+    let mut session = Session::new(countries, translation());
+    session.set_filter(&geo_country::create_regions(&[false, false, false, false, false, false, true], &[]),
+        vec![GameMode::Flags, GameMode::Continents]);
+    session.start(10, Some(2));
+    let mut asked: Vec<String> = Vec::new();
+    while let Some(question) = session.next_question() {
+        //bv has no continent to ask about
+        if question.mode == GameMode::Continents {
+            assert_eq!(question.target.code, "aq");
+            assert_eq!(question.options[question.answer], "Other");
+        }
+        asked.push(question.target.code.clone());
+        session.answer(Answer::TimeOut);
+    }
+    assert!(asked.contains(&"aq".to_string()) && asked.contains(&"bv".to_string()));
+}
+
+#[test]
+fn regions_mix_continents_and_subregions() {
+    let mut countries: Vec<Country> = countries();
+    for country in countries.iter_mut() {
+        country.subregion = match country.code.as_str() {
+            "jp" | "cn" => Some(Subregion::EasternAsia),
+            "fr" | "de" => Some(Subregion::WesternEurope),
+            _ => None,
+        };
+    }
+    let codes = |regions: Regions| -> Vec<String> {
+        geo_country::filter_by_regions(&countries, &regions, IsoFilter::default())
+            .into_iter()
+            .map(|country| country.code)
+            .collect()
+    };
+    assert_eq!(codes(Regions { continents: Vec::new(), subregions: vec![Subregion::WesternEurope] }), vec!["fr", "de"]);
+    assert_eq!(
+        codes(Regions { continents: vec![Continent::Asia], subregions: vec![Subregion::WesternEurope] }),
+        vec!["fr", "de", "jp", "cn"]
+    );
+
+    //every country of the data files is in a subregion, except the places without a continent
+    let data: Vec<Country> = serde_json::from_str(include_str!("../../data/country_en.json")).unwrap();
+    assert!(data
+        .iter()
+        .filter(|country| country.iso && country.continent.is_some())
+        .all(|country| country.subregion.is_some()));
+}
+
+#[test]
+fn old_entries_load_without_the_new_fields() {
+    let old: Country = serde_json::from_str(
+        r#"{"capital": "Paris", "code": "fr", "continent": "Europe", "flag_4x3": "fr.svg", "iso": true, "name": "France"}"#,
+    )
+    .unwrap();
+    assert_eq!(old.population, None);
+    assert_eq!(old.currency, None);
+    assert!(old.borders.is_empty() && old.languages.is_empty());
+
+    let data: Vec<Country> = serde_json::from_str(include_str!("../../data/country_en.json")).unwrap();
+    let france: &Country = geo_country::find_by_code(&data, "fr").unwrap();
+    assert_eq!(france.currency.as_ref().map(|currency| currency.code.as_str()), Some("EUR"));
+    assert_eq!(france.driving_side, Some(DrivingSide::Right));
+
+    //borders go both ways and point to existing entries
+    for country in data.iter() {
+        for code in country.borders.iter() {
+            let neighbour: &Country = geo_country::find_by_code(&data, code).unwrap();
+            assert!(neighbour.borders.contains(&country.code), "{} {}", country.code, code);
+        }
+    }
+    //parts of a country with land borders border the country too
+    for country in data.iter().filter(|country| !country.borders.is_empty()) {
+        if let Some((parent, _)) = country.code.split_once('-') {
+            assert!(country.borders.iter().any(|code| code == parent), "{}", country.code);
+        }
+    }
+}
+
+#[test]
+fn shared_currencies_give_one_right_option() {
+    let mut countries: Vec<Country> = countries();
+    for country in countries.iter_mut() {
+        let code: &str = match country.code.as_str() {
+            "jp" => "JPY",
+            "cn" => "CNY",
+            "aq" => continue,
+            _ => "EUR",
+        };
+        country.currency = Some(Currency { code: code.to_string(), name: code.to_lowercase() });
+    }
+    let currency_of = |name: &str| -> Option<String> {
+        countries.iter().find(|country| country.name == name).and_then(|country| country.currency.clone()).map(|c| c.name)
+    };
+    let mut session = Session::new(countries.clone(), translation());
+    session.set_filter(&Regions::continents(&CONTINENTS), vec![GameMode::Currencies]);
+    session.start(20, Some(9));
+
+    while let Some(question) = session.next_question() {
+        assert_ne!(question.target.code, "aq");
+        let currency: String = question.target.currency.clone().unwrap().name;
+        let right: usize = match &question.prompt {
+            //currency -> country, only one of the euro countries is offered
+            Prompt::Text(text) if *text == currency => question.options
+                .iter()
+                .filter(|option| currency_of(option) == Some(currency.clone()))
+                .count(),
+            _ => question.options.iter().filter(|option| **option == currency).count(),
+        };
+        assert_eq!(right, 1);
+        assert_eq!(question.options.len(), 3);
+        let answer: usize = question.answer;
+        session.answer(Answer::Picked(answer));
+    }
+    assert_eq!(session.score().correct, 20);
+}
+
+#[test]
+fn neighbours_have_one_right_option() {
+    let mut countries: Vec<Country> = countries();
+    let borders = |code: &str| -> Vec<String> {
+        let codes: &[&str] = match code {
+            "fr" => &["de", "it", "es"],
+            "de" => &["fr"],
+            "it" => &["fr"],
+            "es" => &["fr", "pt"],
+            "pt" => &["es"],
+            "cn" => &["va"],
+            "va" => &["cn"],
+            _ => &[],
+        };
+        codes.iter().map(|code| code.to_string()).collect()
+    };
+    for country in countries.iter_mut() {
+        country.borders = borders(&country.code);
+    }
+    let mut session = Session::new(countries.clone(), translation());
+    session.set_filter(&Regions::continents(&CONTINENTS), vec![GameMode::Neighbours]);
+    session.start(30, Some(4));
+
+    let mut asks: Vec<Ask> = Vec::new();
+    while let Some(question) = session.next_question() {
+        //islands like Japan are never asked
+        assert!(!question.target.borders.is_empty());
+        let near: Vec<bool> = question.options
+            .iter()
+            .map(|option| question.target.borders.contains(&option.to_lowercase()))
+            .collect();
+        let right: Vec<bool> = match question.ask {
+            Ask::NotBorders => near.iter().map(|near| !near).collect(),
+            _ => near,
+        };
+        assert_eq!(right.iter().filter(|right| **right).count(), 1);
+        assert!(right[question.answer]);
+        assert!(!question.options.contains(&question.target.name));
+        asks.push(question.ask);
+        session.answer(Answer::TimeOut);
+    }
+    assert!(asks.contains(&Ask::Borders) && asks.contains(&Ask::NotBorders));
+}
+
+#[test]
+fn compare_asks_the_larger_of_two_flags() {
+    let mut countries: Vec<Country> = countries();
+    for (i, country) in countries.iter_mut().enumerate() {
+        if country.code != "aq" {
+            country.population = Some(1000 * (i as u64 + 1));
+            country.area = Some(500.0 - 10.0 * i as f64);
+        }
+    }
+    let mut session = Session::new(countries.clone(), translation());
+    session.set_filter(&Regions::continents(&CONTINENTS), vec![GameMode::Compare]);
+    session.start(12, Some(5));
+
+    let mut asks: Vec<Ask> = Vec::new();
+    while let Some(question) = session.next_question() {
+        assert_eq!(question.prompt, Prompt::Versus);
+        assert_eq!(question.options.len(), 2);
+        assert_eq!(question.option_flags.as_ref().map(Vec::len), Some(2));
+        let pair: Vec<&Country> = question.options
+            .iter()
+            .map(|name| countries.iter().find(|country| country.name == *name).unwrap())
+            .collect();
+        let (right, wrong) = (pair[question.answer], pair[1 - question.answer]);
+        assert_eq!(question.target.code, right.code);
+        assert!(!question.allows_help());
+        match question.ask {
+            Ask::LargerPopulation => assert!(right.population > wrong.population),
+            Ask::LargerArea => assert!(right.area > wrong.area),
+            ask => panic!("{ask:?}"),
+        }
+        asks.push(question.ask);
+        let answer: usize = question.answer;
+        session.answer(Answer::Picked(answer));
+    }
+    assert!(asks.contains(&Ask::LargerPopulation) && asks.contains(&Ask::LargerArea));
+    assert_eq!(session.score().best_streak, 12);
+
+    //the streak bonus makes a perfect run worth more than the plain points
+    let mut plain = geo_game_core::score::Score::default();
+    for _ in 0..12 {
+        plain.correct(session.timer().limit());
+    }
+    assert!(session.score().total() > plain.total());
+}
+
+#[test]
+fn flags_and_capitals_reverse_picks_the_flag() {
+    let mut session = europe_session(vec![GameMode::Fandc], 10);
+    while let Some(question) = session.next_question() {
+        match &question.prompt {
+            Prompt::Text(capital) => {
+                assert_eq!(Some(capital), question.target.capital.as_ref());
+                let flags = question.option_flags.clone().unwrap();
+                assert_eq!(flags[question.answer], question.target.flag_4x3);
+            }
+            Prompt::Flag(_) => {
+                assert!(question.option_flags.is_none());
+                assert_eq!(Some(&question.options[question.answer]), question.target.capital.as_ref());
+            }
+            Prompt::Versus => panic!("versus in flags and capitals"),
+        }
+        session.answer(Answer::TimeOut);
+    }
+}
+
+#[test]
+fn capital_modes_skip_shared_capitals() {
+    let mut countries: Vec<Country> = countries();
+    countries.push(country("gb", Continent::Europe, Some("London")));
+    countries.push(country("gb-eng", Continent::Europe, Some("London")));
+    let mut session = Session::new(countries, translation());
+    session.set_filter(&Regions::continents(&[Continent::Europe]), vec![GameMode::Fandc, GameMode::Capitals, GameMode::ReverseCapitals]);
+    session.start(60, Some(6));
+    while let Some(question) = session.next_question() {
+        let london: Vec<bool> = question.options
+            .iter()
+            .map(|option| option == "London" || option.starts_with("GB"))
+            .collect();
+        assert!(london.iter().filter(|london| **london).count() <= 1, "{:?}", question.options);
+        session.answer(Answer::TimeOut);
+    }
+}
@@ -1,57 +1,34 @@
 use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Deserialize, PartialEq, Clone)]
-pub enum Continent {
-    Africa,
-    Asia,
-    Europe,
-#[serde(rename = "North America")]
-    NorthAmerica,
-#[serde(rename = "South America")]
-    SouthAmerica,
-    Oceania,
-#[serde(other)]
-    Other,
-}
-
-use slint::{SharedString, ToSharedString};
-use crate::translation::ContinentsTranslation;
-
-impl Continent {
-    pub fn ret_continent_name(&self, tr: &ContinentsTranslation) -> SharedString {
-        use Continent::*;
-        match self {
-            Africa => &tr.afr,
-            Asia => &tr.asi,
-            Europe => &tr.eur,
-            NorthAmerica => &tr.nam,
-            SouthAmerica => &tr.sam,
-            Oceania => &tr.oce,
-            Other => &tr.other
-        }.to_shared_string()
-    }
-}
-
-#[derive(Debug, Deserialize, Clone)]
-pub struct Country {
-    pub capital: Option<String>,
-    pub code: String,
-    pub continent: Option<Continent>,
-    pub flag_4x3: String,
-#[allow(dead_code)]
-    pub iso: bool,
-    pub name: String,
-}
+use chrono::NaiveDate;
+use geo_game_core::consts::{ANSWER_NUM, ANSWER_NUMS, TIMER_CHOICES, TIMER_TICKS};
+use geo_game_core::country::{IsoFilter, CONTINENTS, SUBREGIONS};
+use geo_game_core::difficulty::Difficulty;
+use geo_game_core::session::Ending;
+use geo_game_core::gamelogic::{MODE_NUM, Similarity};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct InputConfig {
     pub size: (u32, u32),
     pub position: (i32, i32),
     pub continents: Vec<bool>,
+#[serde(default)]
+    pub subregions: Vec<bool>,
     pub mode: Vec<bool>,
     pub language: String,
     pub color: String,
     pub store: u32,
+#[serde(default)]
+    pub seed: Option<u64>,
+#[serde(default = "InputConfig::default_answers")]
+    pub answers: usize,
+#[serde(default)]
+    pub similarity: Similarity,
+#[serde(default)]
+    pub difficulty: Difficulty,
+#[serde(default = "InputConfig::default_timer")]
+    pub timer: u32,
+#[serde(default)]
+    pub iso: IsoFilter,
 }
 
 impl InputConfig {
@@ -59,24 +36,192 @@ impl InputConfig {
         InputConfig {
             size: (500, 500),
             position: (0, 0),
-            continents: vec![true; 6],
-            mode: vec![true, false, false],
+            continents: vec![true; CONTINENTS.len()],
+            subregions: vec![false; SUBREGIONS.len()],
+            mode: InputConfig::default_mode(),
             language: "en".to_string(),
             color: "gray".to_string(),
             store: 0,
+            seed: None,
+            answers: InputConfig::default_answers(),
+            similarity: Similarity::Random,
+            difficulty: Difficulty::Normal,
+            timer: InputConfig::default_timer(),
+            iso: IsoFilter::default(),
+        }
+    }
+
+    fn default_answers() -> usize {
+        ANSWER_NUM
+    }
+
+    fn default_timer() -> u32 {
+        TIMER_TICKS
+    }
+
+    fn default_mode() -> Vec<bool> {
+        let mut mode: Vec<bool> = vec![false; MODE_NUM];
+        mode[0] = true;
+        mode
+    }
+
+    //config written by older version may have less game modes and regions
+    pub fn normalize(&mut self) {
+        self.mode.resize(MODE_NUM, false);
+        self.continents.resize(CONTINENTS.len(), false);
+        self.subregions.resize(SUBREGIONS.len(), false);
+        if !ANSWER_NUMS.contains(&self.answers) {
+            self.answers = ANSWER_NUM;
+        }
+        if !TIMER_CHOICES.contains(&self.timer) {
+            self.timer = TIMER_TICKS;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StoreRecord {
+//questions in the game or seconds of the time attack
+    pub questions: i32,
+    pub mode: Vec<bool>,
+    pub continents: Vec<bool>,
+#[serde(default)]
+    pub subregions: Vec<bool>,
+#[serde(default)]
+    pub difficulty: Difficulty,
+#[serde(default)]
+    pub ending: Ending,
+#[serde(default)]
+    pub iso: IsoFilter,
+    pub store: u32,
+#[serde(default)]
+    pub seed: u64,
+}
+
+//one high score table
+pub struct StoreKey {
+    pub questions: i32,
+    pub mode: Vec<bool>,
+    pub continents: Vec<bool>,
+    pub subregions: Vec<bool>,
+    pub difficulty: Difficulty,
+    pub ending: Ending,
+    pub iso: IsoFilter,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct StoreTable {
+    pub records: Vec<StoreRecord>,
+}
+
+impl StoreTable {
+    fn find(&self, key: &StoreKey) -> Option<usize> {
+        self.records
+            .iter()
+            .position(|rec| rec.questions == key.questions && rec.mode == key.mode
+                && rec.continents == key.continents && rec.subregions == key.subregions
+                && rec.difficulty == key.difficulty
+                && rec.ending == key.ending && rec.iso == key.iso)
+    }
+
+    pub fn best(&self, key: &StoreKey) -> u32 {
+        match self.find(key) {
+            Some(index) => self.records[index].store,
+            None => 0,
         }
     }
+
+    //records written by older version may have less game modes and regions
+    pub fn normalize(&mut self) {
+        for rec in self.records.iter_mut() {
+            rec.mode.resize(MODE_NUM, false);
+            rec.continents.resize(CONTINENTS.len(), false);
+            rec.subregions.resize(SUBREGIONS.len(), false);
+        }
+    }
+
+    //streaks and time attacks are counted in answers, not in points
+    pub fn best_overall(&self) -> u32 {
+        self.records
+            .iter()
+            .filter(|rec| rec.ending == Ending::Questions)
+            .map(|rec| rec.store)
+            .max()
+            .unwrap_or(0)
+    }
+
+    //returns true when a new record was set
+    pub fn update(&mut self, key: StoreKey, store: u32, seed: u64) -> bool {
+        match self.find(&key) {
+            Some(index) if self.records[index].store >= store => false,
+            Some(index) => {
+                self.records[index].store = store;
+                self.records[index].seed = seed;
+                true
+            }
+            None => {
+                self.records.push(StoreRecord {
+                    questions: key.questions,
+                    mode: key.mode,
+                    continents: key.continents,
+                    subregions: key.subregions,
+                    difficulty: key.difficulty,
+                    ending: key.ending,
+                    iso: key.iso,
+                    store,
+                    seed,
+                });
+                store > 0
+            }
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct DailyRecord {
+    pub day: Option<NaiveDate>,
+    pub store: u32,
+    pub streak: u32,
+}
+
+impl DailyRecord {
+    pub fn played(&self, today: NaiveDate) -> bool {
+        self.day == Some(today)
+    }
+
+    pub fn update(&mut self, today: NaiveDate, store: u32) {
+        self.streak = match self.day {
+            Some(day) if day.succ_opt() == Some(today) => self.streak + 1,
+            Some(day) if day == today => self.streak,
+            _ => 1,
+        };
+        self.day = Some(today);
+        self.store = store;
+    }
 }
 
 pub mod configurationsettings {
     use serde::de::DeserializeOwned;
+    use serde::Serialize;
     use serde_json::Result;
     use std::path::PathBuf;
     use std::fs;
-    use crate::configure::InputConfig;
     use crate::consts::os::*;
+    use crate::consts::data;
 
     pub fn input_config_path() -> PathBuf {
+        config_file_path(CONFIG_FILE)
+    }
+
+    pub fn input_store_path() -> PathBuf {
+        config_file_path(STORE_FILE)
+    }
+
+    pub fn input_daily_path() -> PathBuf {
+        config_file_path(DAILY_FILE)
+    }
+
+    fn config_file_path(file: &str) -> PathBuf {
         let home_dir: PathBuf = match std::env::home_dir(){
             Some(patch) => patch,
             None => panic!("Failed to get home directory!"),
@@ -86,7 +231,7 @@ pub mod configurationsettings {
             fs::create_dir(&config_dir)
                 .expect("Failed to create config directory!");
         }
-        config_dir.join(CONFIG_FILE)
+        config_dir.join(file)
     }
 
     pub fn input_data_path(language: &String, mode: &str, #[cfg(not(debug_assertions))]  patch: &PathBuf) -> PathBuf {
@@ -100,19 +245,33 @@ pub mod configurationsettings {
         }
     }
 
+    pub fn input_flag_colors_path(#[cfg(not(debug_assertions))] patch: &PathBuf) -> PathBuf {
+    #[cfg(debug_assertions)] {
+            PathBuf::from(format!("{LOAD_DATA}{}", data::FLAG_COLORS))
+        }
+    #[cfg(not(debug_assertions))] {
+            patch.join(data::FLAG_COLORS)
+        }
+    }
+
 	pub fn read_from_file<T: DeserializeOwned>(path: &PathBuf) -> Result<T> {
-        let data: String = match fs::read_to_string(path) {
-            Ok(data) => data,
-            Err(_) => String::from(""),
-        };
+        let data: String = fs::read_to_string(path).unwrap_or_default();
         let result = serde_json::from_str(&data)?;
         Ok(result)
     }
 
-    pub fn write_input_config(path: &PathBuf, input: &InputConfig) -> Result<()> {
+    pub fn write_to_file<T: Serialize>(path: &PathBuf, input: &T) -> Result<()> {
         let file: fs::File = fs::File::create(path).unwrap();
-        let output = serde_json::to_writer_pretty(file, input)?;
-        Ok(output)
+        serde_json::to_writer_pretty(file, input)?;
+        Ok(())
+    }
+    //seed passed as `--seed <number>`
+    pub fn cli_seed() -> Option<u64> {
+        let args: Vec<String> = std::env::args().collect();
+        args.iter()
+            .position(|arg| arg == "--seed")
+            .and_then(|index| args.get(index + 1))
+            .and_then(|seed| seed.parse().ok())
     }
 #[cfg(not(debug_assertions))]
     pub fn load_file_ways() -> (PathBuf, PathBuf) {
@@ -128,7 +287,7 @@ pub mod configurationsettings {
 
 pub mod set {
     use slint::{PhysicalPosition, SharedString,
-        PhysicalSize, ModelRc, VecModel, Color};
+        PhysicalSize, ModelRc, VecModel, Color, Image};
 #[cfg(not(debug_assertions))]
     use std::path::PathBuf;
     use std::fs;
@@ -137,6 +296,9 @@ pub mod set {
         MainWindow, EndGame, Information, AnswerData};
     use crate::process::gamelogic;
     use crate::{block_checkbox, drop_rc};
+    use geo_game_core::country::IsoFilter;
+    use geo_game_core::difficulty::{Difficulty, Rules, DIFFICULTIES};
+    use geo_game_core::gamelogic::{Ask, Similarity, SIMILARITIES};
 
 #[inline(always)]
     pub fn scene(window: &MainWindow, scene: i32) {
@@ -151,21 +313,27 @@ pub mod set {
         PhysicalPosition::new(position.0, position.1)
     }
 #[inline(always)]
-    pub fn checkbox_continent_blocked(window: &MainWindow, cont: &Vec<bool>) {
-        let checkbox_blocked: bool = block_checkbox!(&cont, 6);
+    pub fn checkbox_continent_blocked(window: &MainWindow, cont: &Vec<bool>, sub: &Vec<bool>) {
+        //at least one continent or subregion stays checked
+        let regions: Vec<bool> = [cont.as_slice(), sub.as_slice()].concat();
+        let checkbox_blocked: bool = block_checkbox!(&regions, regions.len());
         window.set_checkbox_continent_blocked(checkbox_blocked)
     }
 #[inline(always)]
-    pub fn game_timer_stop(window: &MainWindow) {
-        window.set_run_game_timer(false);
+    pub fn can_play(window: &MainWindow, can: bool) {
+        window.set_can_play(can);
     }
 #[inline(always)]
-    pub fn game_timer_run(window: &MainWindow) {
-        window.set_run_game_timer(true);
+    pub fn daily_done(window: &MainWindow, done: bool) {
+        window.set_daily_done(done);
+    }
+#[inline(always)]
+    pub fn timer_tick(window: &MainWindow, left: u32) {
+        window.set_timer_tick(left as i32);
     }
 #[inline(always)]
     pub fn checkbox_mode_blocked(window: &MainWindow, mode: &Vec<bool>) {
-        let mode_block: bool = block_checkbox!(&mode, 3);
+        let mode_block: bool = block_checkbox!(&mode, mode.len());
         window.set_checkbox_mode_blocked(mode_block)
     }
 #[inline(always)]
@@ -173,13 +341,23 @@ pub mod set {
         let checkbox_model: ModelRc<bool> = drop_rc!(cont);
         window.set_checkbox_continent_checked(checkbox_model);
     }
+#[inline(always)]
+    pub fn checkbox_subregion_checked(window: &MainWindow, sub: Vec<bool>) {
+        let checkbox_model: ModelRc<bool> = drop_rc!(sub);
+        window.set_checkbox_subregion_checked(checkbox_model);
+    }
 #[inline(always)]
     pub fn checkbox_mode_checked(window: &MainWindow, mode: Vec<bool>) {
         let mode_model: ModelRc<bool> = drop_rc!(mode);
         window.set_checkbox_mode_checked(mode_model);
     }
 #[inline(always)]
-    pub fn settings_button_color(window: &MainWindow, color: &String) {
+    pub fn checkbox_iso_checked(window: &MainWindow, iso: IsoFilter) {
+        window.set_checkbox_territories_checked(iso.territories);
+        window.set_checkbox_organisations_checked(iso.organisations);
+    }
+#[inline(always)]
+    pub fn settings_button_color(window: &MainWindow, color: &str) {
         let index: i32 = gamelogic::ret_button_color_index(color);
         let color: Color = gamelogic::ret_button_color(index);
         window.set_selected_button_color_index(index);
@@ -190,7 +368,37 @@ pub mod set {
         window.set_uniq_button_color(gamelogic::ret_button_color(index));
     }
 #[inline(always)]
-    pub fn settings_language(window: &MainWindow, lang: &String) {
+    pub fn settings_seed(window: &MainWindow, seed: Option<u64>) {
+        let text: String = seed.map(|seed| seed.to_string()).unwrap_or_default();
+        window.set_settings_seed(text.into());
+    }
+#[inline(always)]
+    pub fn settings_answers(window: &MainWindow, answers: usize) {
+        let index: usize = ui::ANSWER_NUMS.iter().position(|num| *num == answers).unwrap_or_default();
+        window.set_selected_answers_index(index as i32);
+    }
+#[inline(always)]
+    pub fn settings_timer(window: &MainWindow, timer: u32) {
+        let index: usize = ui::TIMER_CHOICES.iter().position(|ticks| *ticks == timer).unwrap_or_default();
+        window.set_selected_timer_index(index as i32);
+    }
+#[inline(always)]
+    pub fn settings_similarity(window: &MainWindow, similarity: Similarity) {
+        let index: usize = SIMILARITIES.iter().position(|item| *item == similarity).unwrap_or_default();
+        window.set_selected_similarity_index(index as i32);
+    }
+#[inline(always)]
+    pub fn difficulty(window: &MainWindow, difficulty: Difficulty) {
+        let index: usize = DIFFICULTIES.iter().position(|item| *item == difficulty).unwrap_or_default();
+        window.set_difficulty_index(index as i32);
+    }
+#[inline(always)]
+    pub fn game_rules(window: &MainWindow, rules: &Rules) {
+        window.set_timer_tick(rules.ticks as i32);
+        window.set_help_allowed(rules.help);
+    }
+#[inline(always)]
+    pub fn settings_language(window: &MainWindow, lang: &str) {
         let index: i32 = gamelogic::ret_language_index(lang);
         window.set_selected_language_index(index);
     }
@@ -212,26 +420,68 @@ pub mod set {
     }
 use crate::consts::ui;
 #[inline(always)]
-    pub fn enabled_buttons(window: &MainWindow, rand: usize) {
-        let mode: Vec<bool> = gamelogic::get_bad_answers(rand, ui::ANSWER_NUM, ui::BAD_ANSWER);
+    pub fn enabled_buttons(window: &MainWindow, mode: Vec<bool>) {
         window.set_enabled_buttons(drop_rc!(mode));
     }
 #[inline(always)]
-    pub fn reset_enabled_buttons(window: &MainWindow) {
-        window.set_enabled_buttons(drop_rc!(vec![true; ui::ANSWER_NUM]));
+    pub fn ask(window: &MainWindow, ask: Ask) {
+        let ask: i32 = match ask {
+            Ask::Answer => ui::ASK_ANSWER,
+            Ask::Borders => ui::ASK_BORDERS,
+            Ask::NotBorders => ui::ASK_NOT_BORDERS,
+            Ask::LargerPopulation => ui::ASK_POPULATION,
+            Ask::LargerArea => ui::ASK_AREA,
+        };
+        window.set_ask(ask);
+    }
+#[inline(always)]
+    pub fn help_allowed(window: &MainWindow, allowed: bool) {
+        window.set_help_allowed(allowed);
+    }
+#[inline(always)]
+    pub fn text_answer(window: &MainWindow, typed: bool) {
+        window.set_text_answer(typed);
+    }
+#[inline(always)]
+    pub fn reset_enabled_buttons(window: &MainWindow, count: usize) {
+        window.set_enabled_buttons(drop_rc!(vec![true; count]));
     }
 #[inline(always)]
     pub fn game_window_with_image(window: &MainWindow, data: &[u8], model: Vec<SharedString>) {
         use crate::configure::get::img;
+        window.set_versus(false);
+        window.set_flag_answers(false);
         window.set_img_or_text(true);
         window.set_loaded_image(img(data));
         window.set_button_data(drop_rc!(model));
     }
 #[inline(always)]
     pub fn game_window_with_text(window: &MainWindow, text: &SharedString, model: Vec<SharedString>) {
+        window.set_versus(false);
+        window.set_flag_answers(false);
+        window.set_img_or_text(false);
+        window.set_loaded_text(text.clone());
+        window.set_button_data(drop_rc!(model));
+    }
+#[inline(always)]
+    pub fn game_window_with_flags(window: &MainWindow, text: &SharedString, images: &[Vec<u8>], model: Vec<SharedString>) {
+        use crate::configure::get::img;
+        let flags: Vec<Image> = images.iter().map(|data| img(data)).collect();
+        window.set_versus(false);
         window.set_img_or_text(false);
         window.set_loaded_text(text.clone());
+        window.set_button_images(drop_rc!(flags));
         window.set_button_data(drop_rc!(model));
+        window.set_flag_answers(true);
+    }
+#[inline(always)]
+    pub fn game_window_versus(window: &MainWindow, images: &[Vec<u8>], model: Vec<SharedString>) {
+        use crate::configure::get::img;
+        let flags: Vec<Image> = images.iter().map(|data| img(data)).collect();
+        window.set_flag_answers(false);
+        window.set_button_images(drop_rc!(flags));
+        window.set_button_data(drop_rc!(model));
+        window.set_versus(true);
     }
 
     pub fn image_welcome(window: &MainWindow, #[cfg(not(debug_assertions))] patch: &PathBuf) {
@@ -263,8 +513,12 @@ macro_rules! position_bug { // !!! WTF
 
 pub mod get {
     use slint::{Model, Image, PhysicalPosition, PhysicalSize, SharedString};
+    use crate::consts::ui;
     use crate::slint_generatedMainWindow::MainWindow;
     use crate::process::gamelogic;
+    use geo_game_core::country::IsoFilter;
+    use geo_game_core::difficulty::{Difficulty, Rules, DIFFICULTIES};
+    use geo_game_core::gamelogic::{Similarity, SIMILARITIES};
 
 #[inline(always)]
     pub fn window_size(size: PhysicalSize) -> (u32, u32) {
@@ -278,6 +532,11 @@ pub mod get {
     pub fn button_data(window: &MainWindow) -> Vec<SharedString> {
         window.get_button_data().iter().collect()
     }
+#[inline(always)]
+    pub fn settings_timer(window: &MainWindow) -> u32 {
+        let index: usize = window.get_selected_timer_index().max(0) as usize;
+        ui::TIMER_CHOICES.get(index).copied().unwrap_or(ui::TIMER_TICKS)
+    }
 #[inline(always)]
     pub fn settings_button_color(window: &MainWindow) -> String {
         let index: i32 = window.get_selected_button_color_index();
@@ -288,17 +547,57 @@ pub mod get {
         let index: i32 = window.get_selected_language_index();
         gamelogic::ret_language_string(index)
     }
+#[inline(always)]
+    pub fn settings_seed(window: &MainWindow) -> Option<u64> {
+        window.get_settings_seed().trim().parse().ok()
+    }
+#[inline(always)]
+    pub fn settings_answers(window: &MainWindow) -> usize {
+        let index: usize = window.get_selected_answers_index().max(0) as usize;
+        ui::ANSWER_NUMS.get(index).copied().unwrap_or(ui::ANSWER_NUM)
+    }
+#[inline(always)]
+    pub fn settings_similarity(window: &MainWindow) -> Similarity {
+        let index: usize = window.get_selected_similarity_index().max(0) as usize;
+        SIMILARITIES.get(index).copied().unwrap_or_default()
+    }
+#[inline(always)]
+    pub fn difficulty(window: &MainWindow) -> Difficulty {
+        let index: usize = window.get_difficulty_index().max(0) as usize;
+        DIFFICULTIES.get(index).copied().unwrap_or_default()
+    }
+#[inline(always)]
+    pub fn rules(window: &MainWindow) -> Rules {
+        let custom: Rules = Rules {
+            ticks: settings_timer(window),
+            answers: settings_answers(window),
+            similarity: settings_similarity(window),
+            ..Rules::default()
+        };
+        difficulty(window).rules(&custom)
+    }
 #[inline(always)]
     pub fn checkbox_continent_checked(window: &MainWindow) -> Vec<bool> {
         window.get_checkbox_continent_checked().iter().collect()
     }
+#[inline(always)]
+    pub fn checkbox_subregion_checked(window: &MainWindow) -> Vec<bool> {
+        window.get_checkbox_subregion_checked().iter().collect()
+    }
 #[inline(always)]
     pub fn checkbox_mode_checked(window: &MainWindow) -> Vec<bool> {
         window.get_checkbox_mode_checked().iter().collect()
     }
+#[inline(always)]
+    pub fn checkbox_iso_checked(window: &MainWindow) -> IsoFilter {
+        IsoFilter {
+            territories: window.get_checkbox_territories_checked(),
+            organisations: window.get_checkbox_organisations_checked(),
+        }
+    }
 
     pub fn img(image_data: &[u8]) -> Image {
-        match Image::load_from_svg_data(&image_data) {
+        match Image::load_from_svg_data(image_data) {
             Ok(image) => image,
             Err(_) => panic!("Failed to load image"),
         }
@@ -307,8 +606,12 @@ pub mod get {
 
 mod generated {
     use slint::ToSharedString;
+    use std::time::Duration;
     use crate::consts::pallet::RED;
     use crate::slint_generatedMainWindow::{AnswerData, EndGame};
+    use geo_game_core::score::Score;
+    use geo_game_core::difficulty::{Difficulty, DIFFICULTIES};
+    use crate::configure::DailyRecord;
     use crate::null_ss;
 
     impl AnswerData {
@@ -323,13 +626,62 @@ mod generated {
     }
 
     impl EndGame {
-        pub fn my_default() -> Self {
+        pub fn from_score(score: &Score, total: u32, prev_store: u32, new_record: bool, seed: u64,
+            difficulty: Difficulty, average: Option<Duration>) -> Self {
             EndGame {
                 animation: true,
                 timer_run: true,
-                prev_store: 1000,
-                cur_store: 1000
+                new_record,
+                prev_store: prev_store as i32,
+                cur_store: total as i32,
+                correct: score.correct as i32,
+                wrong: score.wrong as i32,
+                time_out: score.time_out as i32,
+                seed: seed.to_shared_string(),
+                streak: 0,
+                best_streak: score.best_streak as i32,
+                difficulty: DIFFICULTIES.iter().position(|item| *item == difficulty).unwrap_or_default() as i32,
+                //average response time, empty when nothing was answered
+                avg_time: match average {
+                    Some(time) => format!("{:.1} s", time.as_secs_f32()).to_shared_string(),
+                    None => "".to_shared_string(),
+                }
             }
         }
+
+        pub fn from_daily(score: &Score, daily: &DailyRecord, seed: u64, average: Option<Duration>) -> Self {
+            let mut game: EndGame = EndGame::from_score(score, score.total(), 0, false, seed, Difficulty::Normal, average);
+            game.streak = daily.streak as i32;
+            game
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_store_keeps_its_records() {
+        //a record written when there were three game modes and six continents
+        let json: &str = r#"{"records": [{"questions": 10, "mode": [true, false, false],
+            "continents": [true, false, false, false, false, false], "store": 42}]}"#;
+        let mut table: StoreTable = serde_json::from_str(json).unwrap();
+        table.normalize();
+
+        let mut mode: Vec<bool> = vec![false; MODE_NUM];
+        mode[0] = true;
+        let mut continents: Vec<bool> = vec![false; CONTINENTS.len()];
+        continents[0] = true;
+        let key: StoreKey = StoreKey {
+            questions: 10,
+            mode,
+            continents,
+            subregions: vec![false; SUBREGIONS.len()],
+            difficulty: Difficulty::default(),
+            ending: Ending::default(),
+            iso: IsoFilter::default(),
+        };
+        assert_eq!(table.best(&key), 42);
     }
-}
\ No newline at end of file
+}
@@ -2,6 +2,8 @@
 pub mod os {
     pub const CONFIG_DIR: &str = ".config/GeoGame";
     pub const CONFIG_FILE: &str = "config.json";
+    pub const STORE_FILE: &str = "store.json";
+    pub const DAILY_FILE: &str = "daily.json";
 #[cfg(debug_assertions)]
     pub const LOAD_DATA: &str = "data/";
 #[cfg(not(debug_assertions))]
@@ -20,6 +22,8 @@ pub mod os {
 pub mod os {
     pub const CONFIG_DIR: &str = "AppData\\Local\\GeoGame";
     pub const CONFIG_FILE: &str = "config.json";
+    pub const STORE_FILE: &str = "store.json";
+    pub const DAILY_FILE: &str = "daily.json";
 #[cfg(debug_assertions)]
     pub const LOAD_DATA: &str = "data\\";
 #[cfg(not(debug_assertions))]
@@ -37,6 +41,7 @@ pub mod os {
 pub mod data {
     pub const DATA: &str = "country_";
     pub const TRANSLATION: &str = "tr_";
+    pub const FLAG_COLORS: &str = "flag_colors.json";
 }
 
 pub mod url {
@@ -69,10 +74,12 @@ pub mod pallet {
 pub mod ui {
 #![allow(dead_code)]
 //in game timer
-    pub const TIME_OUT: i32 = 5;
+    pub const TIME_OUT: i32 = -1;
 //number of answers in game
-    pub const ANSWER_NUM: usize = 4;
-    pub const BAD_ANSWER: usize = 2;
+    pub use geo_game_core::consts::{ANSWER_NUM, ANSWER_NUMS};
+//question timer, ticks are seconds
+    pub use geo_game_core::consts::{TIMER_CHOICES, TIMER_TICKS};
+    pub const TIMER_INTERVAL_MS: u64 = 100;
 //reset
     pub const RESET: i32 = 1;
 //links
@@ -83,10 +90,18 @@ pub mod ui {
     pub const PLAY_10: i32 = 0;
     pub const PLAY_25: i32 = 1;
     pub const PLAY_HARD: i32 = 2;
+    pub const PLAY_DAILY: i32 = 3;
+    pub const PLAY_SUDDEN: i32 = 4;
+    pub const PLAY_ATTACK_60: i32 = 5;
+    pub const PLAY_ATTACK_120: i32 = 6;
 //game mode count
-    pub const PLAY_10_CNT: i32 = 10;
-    pub const PLAY_25_CNT: i32 = 25;
-    pub const PLAY_HARD_CNT: i32 = 99;
+    pub const PLAY_10_CNT: u32 = 10;
+    pub const PLAY_25_CNT: u32 = 25;
+    pub const PLAY_HARD_CNT: u32 = 99;
+    pub const PLAY_DAILY_CNT: u32 = 10;
+//time attack budget in seconds
+    pub const PLAY_ATTACK_60_SEC: u32 = 60;
+    pub const PLAY_ATTACK_120_SEC: u32 = 120;
 //button color
     pub const C_GRAY: i32 = 0;
     pub const C_FREEDOM: i32 = 1;
@@ -94,6 +109,12 @@ pub mod ui {
     pub const C_BLUE_SKY: i32 = 3;
     pub const C_MANDARIN: i32 = 4;
     pub const C_RIPE_LIME: i32 = 5;
+//question asked above the prompt
+    pub const ASK_ANSWER: i32 = 0;
+    pub const ASK_BORDERS: i32 = 1;
+    pub const ASK_NOT_BORDERS: i32 = 2;
+    pub const ASK_POPULATION: i32 = 3;
+    pub const ASK_AREA: i32 = 4;
 //language
     pub const I_EN: i32 = 0;
     pub const I_RU: i32 = 1;
@@ -1,17 +1,25 @@
 use slint::{SharedString, ToSharedString, Weak};
 use std::sync::mpsc::{Sender, Receiver, channel};
 use std::path::PathBuf;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use chrono::{Datelike, Local, NaiveDate};
 use std::rc::Rc;
-
-use process::gamelogic;
+use std::time::{Duration, Instant};
+
+use geo_game_core::country::{self, Country, ContinentsTranslation, IsoFilter};
+use geo_game_core::daily::Daily;
+use geo_game_core::flagcolor::FlagColors;
+use geo_game_core::difficulty::{Difficulty, Rules};
+use geo_game_core::gamelogic::{self, Prompt};
+use geo_game_core::score::Score;
+use geo_game_core::session::{Session, Answer, Ending};
 use consts::*;
 use configure::configurationsettings as ConfSet;
 use translation::TranslationRs;
-use translation::{LocalTranslation, ContinentsTranslation};
+use translation::LocalTranslation;
 use configure::{set, get};
-use configure::{InputConfig, Country, Continent};
-use threadfn::{ThreadIn, ThreadData, GameMode, Action};
+use configure::{InputConfig, StoreKey, StoreTable, DailyRecord};
+use threadfn::{ThreadIn, ThreadData};
 
 mod process;
 mod consts;
@@ -35,6 +43,22 @@ fn main() -> Result<(), slint::PlatformError> {
         Ok(config) => config,
         Err(_) => InputConfig::default(),
     };
+    loaded_config.normalize();
+    //a seed from the command line is used for this run only, it is not saved
+    let cli_seed: Option<u64> = ConfSet::cli_seed();
+    let store_settings = ConfSet::input_store_path();
+    let store_table: Rc<RefCell<StoreTable>> = match ConfSet::read_from_file(&store_settings)
+    {
+        Ok(table) => Rc::new(RefCell::new(table)),
+        Err(_) => Rc::new(RefCell::new(StoreTable::default())),
+    };
+    store_table.borrow_mut().normalize();
+    let daily_settings = ConfSet::input_daily_path();
+    let daily_record: Rc<RefCell<DailyRecord>> = match ConfSet::read_from_file(&daily_settings)
+    {
+        Ok(record) => Rc::new(RefCell::new(record)),
+        Err(_) => Rc::new(RefCell::new(DailyRecord::default())),
+    };
 
     let load_path: PathBuf = ConfSet::input_data_path(&loaded_config.language, data::TRANSLATION, #[cfg(not(debug_assertions))] &data_path_string);
     let tr: TranslationRs = TranslationRs::load_new(&load_path).unwrap();
@@ -42,6 +66,8 @@ fn main() -> Result<(), slint::PlatformError> {
     let continents_tr: ContinentsTranslation = tr.get_continents_translation();
 
     //*  Load app data
+    let load_path: PathBuf = ConfSet::input_flag_colors_path(#[cfg(not(debug_assertions))] &data_path_string);
+    let flag_colors: FlagColors = ConfSet::read_from_file(&load_path).unwrap_or_default();
     let load_path: PathBuf = ConfSet::input_data_path(&loaded_config.language, data::DATA, #[cfg(not(debug_assertions))] &data_path_string);
     let serialized_countries: Vec<Country> = match ConfSet::read_from_file(&load_path)
     {
@@ -55,188 +81,270 @@ fn main() -> Result<(), slint::PlatformError> {
     set::window_language(&main_window, &tr);
     set::settings_language(&main_window, &loaded_config.language);
     set::settings_button_color(&main_window, &loaded_config.color);
-    set::checkbox_continent_blocked(&main_window, &loaded_config.continents);
+    set::settings_seed(&main_window, cli_seed.or(loaded_config.seed));
+    set::settings_answers(&main_window, loaded_config.answers);
+    set::settings_timer(&main_window, loaded_config.timer);
+    set::difficulty(&main_window, loaded_config.difficulty);
+    set::settings_similarity(&main_window, loaded_config.similarity);
+    set::daily_done(&main_window, daily_record.borrow().played(Local::now().date_naive()));
+    set::checkbox_continent_blocked(&main_window, &loaded_config.continents, &loaded_config.subregions);
     set::checkbox_continent_checked(&main_window, loaded_config.continents.clone());
+    set::checkbox_subregion_checked(&main_window, loaded_config.subregions.clone());
     set::checkbox_mode_blocked(&main_window, &loaded_config.mode);
     set::checkbox_mode_checked(&main_window, loaded_config.mode.clone());
+    set::checkbox_iso_checked(&main_window, loaded_config.iso);
 
     let (tx_cmd, rx_cmd): (Sender<ThreadIn>, Receiver<ThreadIn>) = channel();
     let (tx_data, rx_data): (Sender<ThreadData>, Receiver<ThreadData>) = channel();
 
-    //*  Drop thread to filter countries
+    //*  Drop thread to load question data
     use std::thread;
     //? -> Thread
     let _ = thread::spawn({
-        let mut filtered_cont: Vec<Country> = Vec::new();
-        let mut mode: Vec<GameMode> = Vec::new();
-
         move || {
             while let Ok(input) = rx_cmd.recv() {
-                use Action::*;
-                match input.action {
-                    Update => {
-                        let continent: Vec<Continent> = gamelogic::create_continents_list(&input.checkbox.unwrap());
-                        filtered_cont = gamelogic::filter_by_continents(&serialized_countries, &continent);
-                        mode = input.mode.unwrap();
-                    }
-                    Load => {
-                        threadfn::load_data_from_thread(
-                            &filtered_cont,
-                            &mode,
-                            &input,
-                            &tx_data,
-                            &continents_tr,
-                            #[cfg(not(debug_assertions))] &image_path_string
-                        );
-                    }
-                }
+                threadfn::load_data_from_thread(
+                    &input,
+                    &tx_data,
+                    &continents_tr,
+                    #[cfg(not(debug_assertions))] &image_path_string
+                );
             }
         }
     });
     //? <- Thread
 
-    //*  Randomize countries
-    let random_number: Rc<Cell<usize>> = drop_cell!(gamelogic::get_rand_universal(ui::ANSWER_NUM));
-    let max_question_number: Rc<Cell<i32>> = drop_cell!(ui::RESET);
-    let question_number: Rc<Cell<i32>> = drop_cell!(ui::RESET);
+    //*  Game session
+    let session: Rc<RefCell<Session>> = Rc::new(RefCell::new(Session::new(serialized_countries, tr.get_continents_translation())));
+    session.borrow_mut().set_flag_colors(flag_colors);
+    session.borrow_mut().set_rules(&get::rules(&main_window));
+    session.borrow_mut().set_iso_filter(loaded_config.iso);
+    session.borrow_mut().set_filter(
+        &country::create_regions(&loaded_config.continents, &loaded_config.subregions),
+        gamelogic::create_mode_list(&loaded_config.mode)
+    );
+    set::can_play(&main_window, session.borrow().can_start());
 
-    let mode_selected: Vec<GameMode> = gamelogic::create_mode_list(&loaded_config.mode);
-    let _ = Some(tx_cmd.send(ThreadIn {
-        mode: Some(mode_selected),
-        action: Action::Update,
-        checkbox: Some(loaded_config.continents.clone()),
-        random: None
-    }));
+    //* Date of the running daily challenge
+    let daily_day: Rc<Cell<Option<NaiveDate>>> = drop_cell!(None);
 
     //* When click on run button
-    let _ = main_window.on_run_game_process({
-        let tx_cmd_clone: Sender<ThreadIn> = tx_cmd.clone();
-        let random_number_clone: Rc<Cell<usize>> = random_number.clone();
-        let max_question_number_clone: Rc<Cell<i32>> = max_question_number.clone();
-        let question_number_clone: Rc<Cell<i32>> = question_number.clone();
+    main_window.on_run_game_process({
+        let main_window_handle: Weak<MainWindow> = main_window.as_weak();
+        let session_clone: Rc<RefCell<Session>> = session.clone();
+        let daily_day_clone: Rc<Cell<Option<NaiveDate>>> = daily_day.clone();
+        let daily_record_clone: Rc<RefCell<DailyRecord>> = daily_record.clone();
+        let daily_settings_clone: PathBuf = daily_settings.clone();
 
         move |index: i32| {
-            random_number_clone.set(gamelogic::get_rand_universal(ui::ANSWER_NUM));
-            question_number_clone.set(ui::RESET);
-
-            let number: i32 = match index {
-                ui::PLAY_10 => ui::PLAY_10_CNT,
-                ui::PLAY_25 => ui::PLAY_25_CNT,
-                ui::PLAY_HARD => ui::PLAY_HARD_CNT,
-                _ => 0,
-            };
-
-            max_question_number_clone.set(number);
+            let main_window: MainWindow = main_window_handle.unwrap();
+            let mut session_get = session_clone.borrow_mut();
+
+            if index == ui::PLAY_DAILY {
+                let today: NaiveDate = Local::now().date_naive();
+                let daily: Daily = Daily::from_date(today.year(), today.month(), today.day());
+                let rules: Rules = Rules::default();
+                session_get.set_rules(&rules);
+                session_get.set_ending(Ending::Questions);
+                session_get.set_iso_filter(IsoFilter::default());
+                session_get.set_filter(&country::Regions::continents(&country::CONTINENTS), daily.modes);
+                set::game_rules(&main_window, &rules);
+                session_get.start(ui::PLAY_DAILY_CNT, Some(daily.seed));
+                //the attempt is counted as soon as the game starts, quitting does not give another try
+                let mut record = daily_record_clone.borrow_mut();
+                record.update(today, 0);
+                ConfSet::write_to_file(&daily_settings_clone, &*record).unwrap();
+                set::daily_done(&main_window, true);
+                daily_day_clone.set(Some(today));
+                return;
+            }
 
-            let _ = Some(tx_cmd_clone.send(ThreadIn {
-                mode: None,
-                action: Action::Load,
-                checkbox: None,
-                random: Some(random_number_clone.get())
-            }));
+            let (ending, number): (Ending, u32) = match index {
+                ui::PLAY_10 => (Ending::Questions, ui::PLAY_10_CNT),
+                ui::PLAY_25 => (Ending::Questions, ui::PLAY_25_CNT),
+                ui::PLAY_HARD => (Ending::Questions, ui::PLAY_HARD_CNT),
+                ui::PLAY_SUDDEN => (Ending::SuddenDeath, 0),
+                ui::PLAY_ATTACK_60 => (Ending::TimeAttack, ui::PLAY_ATTACK_60_SEC),
+                ui::PLAY_ATTACK_120 => (Ending::TimeAttack, ui::PLAY_ATTACK_120_SEC),
+                _ => (Ending::Questions, 0),
+            };
+            let mut rules: Rules = get::rules(&main_window);
+            rules.help &= ending.allows_help();
+            session_get.set_rules(&rules);
+            session_get.set_ending(ending);
+            session_get.set_iso_filter(get::checkbox_iso_checked(&main_window));
+            session_get.set_filter(
+                &country::create_regions(&get::checkbox_continent_checked(&main_window), &get::checkbox_subregion_checked(&main_window)),
+                gamelogic::create_mode_list(&get::checkbox_mode_checked(&main_window))
+            );
+            set::game_rules(&main_window, &rules);
+            session_get.start(number, get::settings_seed(&main_window));
+            set::timer_tick(&main_window, session_get.time_left());
+            daily_day_clone.set(None);
         }
     });
 
     //* When click on country button
-    let _ = main_window.on_button_clicked({
+    main_window.on_button_clicked({
         let main_window_handle: Weak<MainWindow> = main_window.as_weak();
-        let tx_cmd_clone: Sender<ThreadIn> = tx_cmd.clone();
-        let random_number_clone: Rc<Cell<usize>> = random_number.clone();
+        let session_clone: Rc<RefCell<Session>> = session.clone();
 
         move |index: i32| {
             let main_window: MainWindow = main_window_handle.unwrap();
             let input_names: Vec<SharedString> = get::button_data(&main_window);
             let mut model: AnswerData = AnswerData::my_default();
-            let random_number_get: usize = random_number_clone.get();
+            let mut session_get = session_clone.borrow_mut();
 
-            match index {
-                ui::TIME_OUT => {
-                    model.selected = to_ss!(local_tr.time_out);
-                    model.answer = input_names[random_number_get].clone();
-                },
-                _ => {
-                    if index as usize == random_number_get { model.color = pallet::GREEN; }
-                    model.selected = input_names[index as usize].clone();
-                    model.answer = input_names[random_number_get].clone();
-                }
-            }
+            let answer: Answer = match index {
+                ui::TIME_OUT => Answer::TimeOut,
+                _ => Answer::Picked(index as usize),
+            };
+            let Some(outcome) = session_get.answer(answer) else { return };
+
+            model.selected = match outcome.selected {
+                Some(selected) => input_names[selected].clone(),
+                None => to_ss!(local_tr.time_out),
+            };
+            model.answer = input_names[outcome.answer].clone();
+            if outcome.correct { model.color = pallet::GREEN; }
+            set::answer_data(&main_window, model);
+        }
+    });
+
+    //* When enter the answer in typing mode
+    main_window.on_text_answered({
+        let main_window_handle: Weak<MainWindow> = main_window.as_weak();
+        let session_clone: Rc<RefCell<Session>> = session.clone();
+
+        move |text: SharedString| {
+            let main_window: MainWindow = main_window_handle.unwrap();
+            let input_names: Vec<SharedString> = get::button_data(&main_window);
+            let mut model: AnswerData = AnswerData::my_default();
+            let mut session_get = session_clone.borrow_mut();
+
+            let Some(outcome) = session_get.answer(Answer::Typed(text.to_string())) else { return };
+
+            model.selected = text;
+            model.answer = input_names[outcome.answer].clone();
+            if outcome.correct { model.color = pallet::GREEN; }
             set::answer_data(&main_window, model);
-            random_number_clone.set(gamelogic::get_rand_universal(ui::ANSWER_NUM));
-
-            let _ = Some(tx_cmd_clone.send(ThreadIn {
-                mode: None,
-                action: Action::Load,
-                checkbox: None,
-                random: Some(random_number_clone.get())
-            }));
         }
     });
 
-    //* When click on continent checkbox
-    let _ = main_window.on_checkbox_clicked({
+    //* When click on continent or subregion checkbox
+    main_window.on_checkbox_clicked({
         let main_window_handle: Weak<MainWindow> = main_window.as_weak();
+        let session_clone: Rc<RefCell<Session>> = session.clone();
 
         move || {
             let main_window: MainWindow = main_window_handle.unwrap();
             let checkbox: Vec<bool> = get::checkbox_continent_checked(&main_window);
+            let subregions: Vec<bool> = get::checkbox_subregion_checked(&main_window);
             let mode: Vec<bool> = get::checkbox_mode_checked(&main_window);
-            set::checkbox_continent_blocked(&main_window, &checkbox);
+            set::checkbox_continent_blocked(&main_window, &checkbox, &subregions);
             set::checkbox_mode_blocked(&main_window, &mode);
 
-            let mode_selected: Vec<GameMode> = gamelogic::create_mode_list(&mode);
-            let _ = Some(tx_cmd.send(ThreadIn {
-                mode: Some(mode_selected),
-                action: Action::Update,
-                checkbox: Some(checkbox),
-                random: None
-            }));
+            let mut session_get = session_clone.borrow_mut();
+            //the difficulty may leave out the territories
+            session_get.set_rules(&get::rules(&main_window));
+            session_get.set_iso_filter(get::checkbox_iso_checked(&main_window));
+            session_get.set_filter(
+                &country::create_regions(&checkbox, &subregions),
+                gamelogic::create_mode_list(&mode)
+            );
+            set::can_play(&main_window, session_get.can_start());
         }
     });
 
     //* When update window after selected country
-    let _ = main_window.on_update_window({
+    main_window.on_update_window({
         use consts::ui::scene;
         let main_window_handle: Weak<MainWindow> = main_window.as_weak();
+        let session_clone: Rc<RefCell<Session>> = session.clone();
+        let store_table_clone: Rc<RefCell<StoreTable>> = store_table.clone();
 
         move || {
             let main_window: MainWindow = main_window_handle.unwrap();
-
-            if let Ok(input) = rx_data.recv() {
-                let q_num: i32 = question_number.get();
-                let m_q_num: i32 = max_question_number.get();
-                if m_q_num < q_num {
-                    let game: EndGame  = EndGame::my_default();
-                    set::game_timer_stop(&main_window);
-                    set::scene(&main_window, scene::END_GAME_WINDOW);
-                    set::end_game_events(&main_window, game);
+            let mut session_get = session_clone.borrow_mut();
+
+            let Some(question) = session_get.next_question().cloned() else {
+                let score: Score = *session_get.score();
+                let seed: u64 = session_get.seed();
+                let average: Option<Duration> = session_get.average_response();
+                set::scene(&main_window, scene::END_GAME_WINDOW);
+
+                if let Some(today) = daily_day.take() {
+                    let mut record = daily_record.borrow_mut();
+                    record.update(today, score.total());
+                    ConfSet::write_to_file(&daily_settings, &*record).unwrap();
+                    set::daily_done(&main_window, true);
+                    set::end_game_events(&main_window, EndGame::from_daily(&score, &record, seed, average));
                     return;
                 }
-                use GameMode::*;
-                match input.mode {
-                    Flags => {
+
+                let total: u32 = session_get.total();
+                let mut table = store_table_clone.borrow_mut();
+                let difficulty: Difficulty = get::difficulty(&main_window);
+                let key: StoreKey = StoreKey {
+                    questions: session_get.length() as i32,
+                    mode: get::checkbox_mode_checked(&main_window),
+                    continents: get::checkbox_continent_checked(&main_window),
+                    subregions: get::checkbox_subregion_checked(&main_window),
+                    difficulty,
+                    ending: session_get.ending(),
+                    iso: get::checkbox_iso_checked(&main_window),
+                };
+                let prev_store: u32 = table.best(&key);
+                //a game without questions is not a record
+                let new_record: bool = session_get.number() > 0 && table.update(key, total, seed);
+                if new_record {
+                    ConfSet::write_to_file(&store_settings, &*table).unwrap();
+                }
+
+                let game: EndGame  = EndGame::from_score(&score, total, prev_store, new_record, seed, difficulty, average);
+                set::end_game_events(&main_window, game);
+                return;
+            };
+
+            let neighbours: Vec<String> = session_get.neighbours(&question.target)
+                .iter()
+                .map(|country| country.name.clone())
+                .collect();
+            tx_cmd.send(ThreadIn { question, neighbours }).unwrap();
+            if let Ok(input) = rx_data.recv() {
+                let answers: usize = input.names.len();
+                match &input.prompt {
+                    Prompt::Flag(_) => {
                         set::game_window_with_image(&main_window, &input.data.img, input.names);
                     }
-                    Capitals => {
-                        set::game_window_with_text(&main_window, &input.data.name, input.names);
+                    Prompt::Text(text) if !input.images.is_empty() => {
+                        set::game_window_with_flags(&main_window, &to_ss!(text), &input.images, input.names);
+                    }
+                    Prompt::Text(text) => {
+                        set::game_window_with_text(&main_window, &to_ss!(text), input.names);
                     }
-                    Fandc => {
-                        main_window.set_img_or_text(false);
-                        println!("FandC");
+                    Prompt::Versus => {
+                        set::game_window_versus(&main_window, &input.images, input.names);
                     }
                 }
-                set::reset_enabled_buttons(&main_window);
-
-                let question: SharedString = to_ss!(format!("{}/{}", q_num, m_q_num));
+                set::reset_enabled_buttons(&main_window, answers);
+                set::text_answer(&main_window, input.typed);
+                set::ask(&main_window, input.ask);
+                set::help_allowed(&main_window, session_get.help_available());
+
+                let question: SharedString = match session_get.ending() {
+                    Ending::Questions => to_ss!(format!("{}/{}", session_get.number(), session_get.length())),
+                    Ending::SuddenDeath | Ending::TimeAttack => to_ss!(session_get.number()),
+                };
                 set::question_number(&main_window, question);
-                set::game_timer_run(&main_window);
-                question_number.set(q_num + 1);
+                set::timer_tick(&main_window, session_get.time_left());
                 set::info_about_country(&main_window, input.data.to_info());
             }
         }
     });
 
     //* When click on info button in "About" window
-    let _ = main_window.on_open_url_info({
+    main_window.on_open_url_info({
         move |index: i32| {
             match index {
                 ui::LINK_GITHUB => open::that(url::GITHUB).unwrap(),
@@ -248,7 +356,7 @@ fn main() -> Result<(), slint::PlatformError> {
     });
 
     //* Select button color
-    let _ = main_window.on_selected_button_color({
+    main_window.on_selected_button_color({
         let main_window_handle: Weak<MainWindow> = main_window.as_weak();
 
         move |index: i32| {
@@ -257,18 +365,55 @@ fn main() -> Result<(), slint::PlatformError> {
         }
     });
 
+    //* Question timer, counts only while the question is asked and the game is not paused
+    let game_timer: slint::Timer = slint::Timer::default();
+    game_timer.start(slint::TimerMode::Repeated, Duration::from_millis(ui::TIMER_INTERVAL_MS), {
+        let main_window_handle: Weak<MainWindow> = main_window.as_weak();
+        let session_clone: Rc<RefCell<Session>> = session.clone();
+        let last_tick: Cell<Instant> = Cell::new(Instant::now());
+
+        move || {
+            let main_window: MainWindow = main_window_handle.unwrap();
+            let now: Instant = Instant::now();
+            let delta: Duration = now - last_tick.replace(now);
+
+            let mut session_get = session_clone.borrow_mut();
+            let expired: bool = session_get.tick(delta);
+            set::timer_tick(&main_window, session_get.time_left());
+            drop(session_get);
+
+            if expired {
+                main_window.invoke_button_clicked(ui::TIME_OUT);
+            }
+        }
+    });
+
+    //* When open or close the exit dialog during the game
+    main_window.on_game_paused({
+        let session_clone: Rc<RefCell<Session>> = session.clone();
+
+        move |paused: bool| {
+            let mut session_get = session_clone.borrow_mut();
+            match paused {
+                true => session_get.pause(),
+                false => session_get.resume(),
+            }
+        }
+    });
+
     //* Help
-    let _ = main_window.on_help_chance({
+    main_window.on_help_chance({
         let main_window_handle: Weak<MainWindow> = main_window.as_weak();
 
         move || {
             let main_window: MainWindow = main_window_handle.unwrap();
-            set::enabled_buttons(&main_window, random_number.get());
+            let enabled: Vec<bool> = session.borrow_mut().help();
+            set::enabled_buttons(&main_window, enabled);
         }
     });
 
     //* When close window
-    let _ = main_window.window().on_close_requested({
+    main_window.window().on_close_requested({
         let main_window_handle: Weak<MainWindow> = main_window.as_weak();
 
         move || {
@@ -277,11 +422,22 @@ fn main() -> Result<(), slint::PlatformError> {
             loaded_config.size = get::window_size(main_window.window().size());
             loaded_config.position = get::window_position(main_window.window().position());
             loaded_config.continents = get::checkbox_continent_checked(&main_window);
+            loaded_config.subregions = get::checkbox_subregion_checked(&main_window);
             loaded_config.mode = get::checkbox_mode_checked(&main_window);
+            loaded_config.iso = get::checkbox_iso_checked(&main_window);
             loaded_config.language = get::settings_language(&main_window);
             loaded_config.color = get::settings_button_color(&main_window);
+            let seed: Option<u64> = get::settings_seed(&main_window);
+            if cli_seed.is_none() || seed != cli_seed {
+                loaded_config.seed = seed;
+            }
+            loaded_config.answers = get::settings_answers(&main_window);
+            loaded_config.timer = get::settings_timer(&main_window);
+            loaded_config.similarity = get::settings_similarity(&main_window);
+            loaded_config.difficulty = get::difficulty(&main_window);
+            loaded_config.store = store_table.borrow().best_overall();
 
-            ConfSet::write_input_config(&conf_settings, &loaded_config).unwrap();
+            ConfSet::write_to_file(&conf_settings, &loaded_config).unwrap();
             slint::CloseRequestResponse::HideWindow
         }
     });
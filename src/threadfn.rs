@@ -5,29 +5,22 @@ use std::fs;
 use std::path::PathBuf;
 #[cfg(debug_assertions)]
 use crate::consts::os::LOAD_IMAGE;
-use crate::consts::ui;
-use crate::process::gamelogic;
-use crate::configure::Country;
+use geo_game_core::country::{Country, DrivingSide};
+use geo_game_core::gamelogic::{Ask, Prompt, Question};
 use crate::null_ss;
 
-#[derive(PartialEq)]
-pub enum Action {
-    Update,
-    Load
-}
-
-#[derive(PartialEq, Clone)]
-pub enum GameMode {
-    Flags,
-    Capitals,
-    Fandc
-}
-
 pub struct CountryData {
     pub name: SharedString,
     pub capital: SharedString,
     pub code: SharedString,
     pub continent: SharedString,
+    pub population: SharedString,
+    pub area: SharedString,
+    pub currency: SharedString,
+    pub languages: SharedString,
+    pub calling_code: SharedString,
+    pub driving_side: SharedString,
+    pub borders: SharedString,
     pub img: Vec<u8>,
 }
 
@@ -41,99 +34,163 @@ impl CountryData {
             capital: null_ss!(),
             code: null_ss!(),
             continent: null_ss!(),
+            population: null_ss!(),
+            area: null_ss!(),
+            currency: null_ss!(),
+            languages: null_ss!(),
+            calling_code: null_ss!(),
+            driving_side: null_ss!(),
+            borders: null_ss!(),
             img: vec![1;1],
         }
     }
 
-    pub fn to_info(self) -> Information {
+    pub fn to_info(&self) -> Information {
         Information {
-            name: self.name,
-            capital: self.capital,
-            code: self.code,
-            continent: self.continent,
+            name: self.name.clone(),
+            capital: self.capital.clone(),
+            code: self.code.clone(),
+            continent: self.continent.clone(),
+            population: self.population.clone(),
+            area: self.area.clone(),
+            currency: self.currency.clone(),
+            languages: self.languages.clone(),
+            calling_code: self.calling_code.clone(),
+            driving_side: self.driving_side.clone(),
+            borders: self.borders.clone(),
             img: get::img(&self.img)
         }
     }
 }
 
 pub struct ThreadData {
-    pub mode: GameMode,
+    pub prompt: Prompt,
+    pub ask: Ask,
     pub data: CountryData,
-    pub names: Vec<SharedString>
+    pub names: Vec<SharedString>,
+    //flags of the answers, empty for text answers
+    pub images: Vec<Vec<u8>>,
+    pub typed: bool
 }
 
 pub struct ThreadIn {
-    pub mode: Option<Vec<GameMode>>,
-    pub action: Action,
-    pub checkbox: Option<Vec<bool>>,
-    pub random: Option<usize>
+    pub question: Question,
+    //names of the countries bordering the target
+    pub neighbours: Vec<String>
+}
+
+use geo_game_core::country::ContinentsTranslation;
+
+#[inline(always)]
+fn capital_or_null(country: &Country) -> SharedString {
+    match &country.capital {
+        Some(capital) => capital.to_shared_string(),
+        None => null_ss!()
+    }
+}
+
+//1234567 -> "1 234 567"
+fn group_digits(number: u64) -> String {
+    let digits: String = number.to_string();
+    let mut out: String = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(' ');
+        }
+        out.push(digit);
+    }
+    out
+}
+
+#[inline(always)]
+fn area_or_null(country: &Country) -> SharedString {
+    match country.area {
+        Some(area) if area < 10.0 => format!("{area} km²").to_shared_string(),
+        Some(area) => format!("{} km²", group_digits(area.round() as u64)).to_shared_string(),
+        None => null_ss!()
+    }
+}
+
+#[inline(always)]
+fn list_or_null(list: &[String]) -> SharedString {
+    match list.is_empty() {
+        true => null_ss!(),
+        false => list.join(", ").to_shared_string()
+    }
 }
 
-use crate::translation::ContinentsTranslation;
+fn load_image(file: &str, #[cfg(not(debug_assertions))] image_path_string: &PathBuf) -> Vec<u8> {
+#[cfg(debug_assertions)]
+    let patch: String = format!("{LOAD_IMAGE}{}", file);
+#[cfg(not(debug_assertions))]
+    let patch: PathBuf = image_path_string.join(file);
+    match fs::read(patch) {
+        Ok(data) => data,
+        Err(_) => panic!("Failed to load image")
+    }
+}
 
 #[inline(always)]
 pub fn load_data_from_thread(
-    filtered_cont: &Vec<Country>,
-    mode: &Vec<GameMode>,
     input: &ThreadIn,
     tx_data: &Sender<ThreadData>,
     tr_cont: &ContinentsTranslation,
 #[cfg(not(debug_assertions))]
     image_path_string: &PathBuf
 ) {
-    let mut model: Vec<SharedString> = vec![SharedString::new(); ui::ANSWER_NUM];
-    let used_countries: Vec<Country> = gamelogic::get_random_countries(&filtered_cont, ui::ANSWER_NUM);
-    let used_mode: GameMode = mode[gamelogic::get_rand_universal(mode.len())].clone();
+    let target: &Country = &input.question.target;
+    let model: Vec<SharedString> = input.question.options
+        .iter()
+        .map(|option| option.to_shared_string())
+        .collect();
     let mut data_out: CountryData = CountryData::default();
 
-    use GameMode::*;
-    match used_mode {
-        Flags => {
-            for i in 0..ui::ANSWER_NUM { model[i] = used_countries[i].name.to_shared_string(); }
-        }
-        Capitals => {
-            for i in 0..ui::ANSWER_NUM {
-                let exit = match &used_countries[i].capital {
-                    None => { null_ss!() },
-                    Some(capital) => { capital.to_shared_string() }
-                };
-                model[i] = exit;
-            }
-        }
-        Fandc => {
-            println!("TODO: FandC");
-        }
-    }
-
-    let rand_unwrap: usize = input.random.unwrap();
-#[cfg(debug_assertions)]
-    let patch: String = used_countries[rand_unwrap].flag_4x3.to_string();
-#[cfg(debug_assertions)]
-    let patch: String = format!("{LOAD_IMAGE}{}", patch);
-#[cfg(not(debug_assertions))]
-    let patch: PathBuf = image_path_string.join(used_countries[rand_unwrap].flag_4x3.as_str());
-    let image_data: Vec<u8> = match fs::read(patch) {
-        Ok(data) => data,
-        Err(_) => panic!("Failed to load image")
+    let image_data: Vec<u8> = load_image(&target.flag_4x3, #[cfg(not(debug_assertions))] image_path_string);
+    let images: Vec<Vec<u8>> = match &input.question.option_flags {
+        Some(flags) => flags
+            .iter()
+            .map(|flag| load_image(flag, #[cfg(not(debug_assertions))] image_path_string))
+            .collect(),
+        None => Vec::new(),
     };
 
-    data_out.name = used_countries[rand_unwrap].name.to_shared_string();
-    data_out.capital = match &used_countries[rand_unwrap].capital {
-        Some(capital) => { capital.to_shared_string() },
+    data_out.name = target.name.to_shared_string();
+    data_out.capital = capital_or_null(target);
+    data_out.code = target.code.to_shared_string();
+    data_out.continent = match &target.continent {
+        Some(continent) => { continent.ret_continent_name(tr_cont).to_shared_string() },
         None => { null_ss!() }
     };
-    data_out.code = used_countries[rand_unwrap].code.to_shared_string();
-    data_out.continent = match &used_countries[rand_unwrap].continent {
-        Some(continent) => { continent.ret_continent_name(&tr_cont) },
-        None => { null_ss!() }
+    data_out.population = match target.population {
+        Some(population) => group_digits(population).to_shared_string(),
+        None => null_ss!()
+    };
+    data_out.area = area_or_null(target);
+    data_out.currency = match &target.currency {
+        Some(currency) => format!("{} ({})", currency.name, currency.code).to_shared_string(),
+        None => null_ss!()
+    };
+    data_out.languages = list_or_null(&target.languages);
+    data_out.calling_code = match &target.calling_code {
+        Some(code) => code.to_shared_string(),
+        None => null_ss!()
     };
+    //translated on the slint side
+    data_out.driving_side = match target.driving_side {
+        Some(DrivingSide::Left) => "left".to_shared_string(),
+        Some(DrivingSide::Right) => "right".to_shared_string(),
+        None => null_ss!()
+    };
+    data_out.borders = list_or_null(&input.neighbours);
     data_out.img = image_data;
-    //data_out.iso = used_countries[rand_unwrap].iso;
 
     let data: ThreadData = ThreadData {
-        mode: used_mode,
+        prompt: input.question.prompt.clone(),
+        ask: input.question.ask,
         data: data_out,
-        names: model
+        names: model,
+        images,
+        typed: input.question.typed
     };
     tx_data.send(data).unwrap();
-}
\ No newline at end of file
+}
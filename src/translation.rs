@@ -3,6 +3,7 @@ use serde_json::Result;
 use std::path::PathBuf;
 use std::fs;
 use slint::ToSharedString;
+use geo_game_core::country::ContinentsTranslation;
 use crate::slint_generatedMainWindow::Translation;
 use crate::to_ss;
 
@@ -15,12 +16,30 @@ pub struct TranslationRs {
     about: String,
 
     end: String,
+    best: String,
+    new_rec: String,
+    seed: String,
+    streak: String,
     close: String,
     name: String,
     capital: String,
     continent: String,
     code: String,
+    population: String,
+    area: String,
+    currency: String,
+    languages: String,
+    calling_code: String,
+    driving: String,
+    drive_left: String,
+    drive_right: String,
+    borders: String,
     answer: String,
+    ask_borders: String,
+    ask_not_borders: String,
+    ask_population: String,
+    ask_area: String,
+    type_answer: String,
     info: String,
     help: String,
     exit_o_p: String,
@@ -33,18 +52,71 @@ pub struct TranslationRs {
     nam: String,
     sam: String,
     oce: String,
+    oth: String,
+    sel_subreg: String,
+    e_europe: String,
+    n_europe: String,
+    s_europe: String,
+    w_europe: String,
+    c_asia: String,
+    e_asia: String,
+    se_asia: String,
+    s_asia: String,
+    w_asia: String,
+    n_africa: String,
+    e_africa: String,
+    m_africa: String,
+    s_africa: String,
+    w_africa: String,
+    n_america: String,
+    c_america: String,
+    caribbean: String,
+    s_america: String,
+    anz: String,
+    melanesia: String,
+    micronesia: String,
+    polynesia: String,
 
     flags: String,
     capitals: String,
     flag_and_c: String,
+    r_flags: String,
+    r_capitals: String,
+    continents: String,
+    codes: String,
+    typing: String,
+    currencies: String,
+    neighbours: String,
+    compare: String,
     sel_pref_reg: String,
     sel_mode: String,
+    sel_iso: String,
+    territories: String,
+    organisations: String,
+    difficulty: String,
+    easy: String,
+    normal: String,
+    hard: String,
+    custom: String,
+    custom_only: String,
+    no_questions: String,
     p_10_n: String,
     p_25_n: String,
     p_hard: String,
+    p_sudden: String,
+    p_attack: String,
+    daily: String,
 
     sel_b_color: String,
     sel_lang: String,
+    sel_answers: String,
+    sel_timer: String,
+    sel_distr: String,
+    sel_seed: String,
+    random: String,
+    d_continent: String,
+    d_colours: String,
+    d_spelling: String,
 
     time_out: String,
 }
@@ -53,22 +125,9 @@ pub struct LocalTranslation {
     pub time_out: String,
 }
 
-pub struct ContinentsTranslation {
-    pub eur: String,
-    pub asi: String,
-    pub afr: String,
-    pub nam: String,
-    pub sam: String,
-    pub oce: String,
-    pub other: String
-}
-
 impl TranslationRs {
     pub fn load_new(patch: &PathBuf) -> Result<Self> {
-        let data: String = match fs::read_to_string(patch) {
-            Ok(data) => data,
-            Err(_) => String::from(""),
-        };
+        let data: String = fs::read_to_string(patch).unwrap_or_default();
         let result: TranslationRs = serde_json::from_str(&data)?;
         Ok(result)
     }
@@ -87,7 +146,7 @@ impl TranslationRs {
             nam: self.nam.clone(),
             sam: self.sam.clone(),
             oce: self.oce.clone(),
-            other: "Other".to_string(),
+            other: self.oth.clone(),
         }
     }
 
@@ -100,12 +159,30 @@ impl TranslationRs {
             about: to_ss!(self.about),
 
             end: to_ss!(self.end),
+            best: to_ss!(self.best),
+            new_rec: to_ss!(self.new_rec),
+            seed: to_ss!(self.seed),
+            streak: to_ss!(self.streak),
             close: to_ss!(self.close),
             name: to_ss!(self.name),
             capital: to_ss!(self.capital),
             continent: to_ss!(self.continent),
             code: to_ss!(self.code),
+            population: to_ss!(self.population),
+            area: to_ss!(self.area),
+            currency: to_ss!(self.currency),
+            languages: to_ss!(self.languages),
+            calling_code: to_ss!(self.calling_code),
+            driving: to_ss!(self.driving),
+            drive_left: to_ss!(self.drive_left),
+            drive_right: to_ss!(self.drive_right),
+            borders: to_ss!(self.borders),
             answer: to_ss!(self.answer),
+            ask_borders: to_ss!(self.ask_borders),
+            ask_not_borders: to_ss!(self.ask_not_borders),
+            ask_population: to_ss!(self.ask_population),
+            ask_area: to_ss!(self.ask_area),
+            type_answer: to_ss!(self.type_answer),
             info: to_ss!(self.info),
             help: to_ss!(self.help),
             exit_o_p: to_ss!(self.exit_o_p),
@@ -118,18 +195,71 @@ impl TranslationRs {
             nam: to_ss!(self.nam),
             sam: to_ss!(self.sam),
             oce: to_ss!(self.oce),
+            oth: to_ss!(self.oth),
+            sel_subreg: to_ss!(self.sel_subreg),
+            e_europe: to_ss!(self.e_europe),
+            n_europe: to_ss!(self.n_europe),
+            s_europe: to_ss!(self.s_europe),
+            w_europe: to_ss!(self.w_europe),
+            c_asia: to_ss!(self.c_asia),
+            e_asia: to_ss!(self.e_asia),
+            se_asia: to_ss!(self.se_asia),
+            s_asia: to_ss!(self.s_asia),
+            w_asia: to_ss!(self.w_asia),
+            n_africa: to_ss!(self.n_africa),
+            e_africa: to_ss!(self.e_africa),
+            m_africa: to_ss!(self.m_africa),
+            s_africa: to_ss!(self.s_africa),
+            w_africa: to_ss!(self.w_africa),
+            n_america: to_ss!(self.n_america),
+            c_america: to_ss!(self.c_america),
+            caribbean: to_ss!(self.caribbean),
+            s_america: to_ss!(self.s_america),
+            anz: to_ss!(self.anz),
+            melanesia: to_ss!(self.melanesia),
+            micronesia: to_ss!(self.micronesia),
+            polynesia: to_ss!(self.polynesia),
 
             flags: to_ss!(self.flags),
             capitals: to_ss!(self.capitals),
             flag_and_c: to_ss!(self.flag_and_c),
+            r_flags: to_ss!(self.r_flags),
+            r_capitals: to_ss!(self.r_capitals),
+            continents: to_ss!(self.continents),
+            codes: to_ss!(self.codes),
+            typing: to_ss!(self.typing),
+            currencies: to_ss!(self.currencies),
+            neighbours: to_ss!(self.neighbours),
+            compare: to_ss!(self.compare),
             sel_pref_reg: to_ss!(self.sel_pref_reg),
             sel_mode: to_ss!(self.sel_mode),
+            sel_iso: to_ss!(self.sel_iso),
+            territories: to_ss!(self.territories),
+            organisations: to_ss!(self.organisations),
+            difficulty: to_ss!(self.difficulty),
+            easy: to_ss!(self.easy),
+            normal: to_ss!(self.normal),
+            hard: to_ss!(self.hard),
+            custom: to_ss!(self.custom),
+            custom_only: to_ss!(self.custom_only),
+            no_questions: to_ss!(self.no_questions),
             p_10_n: to_ss!(self.p_10_n),
             p_25_n: to_ss!(self.p_25_n),
             p_hard: to_ss!(self.p_hard),
+            p_sudden: to_ss!(self.p_sudden),
+            p_attack: to_ss!(self.p_attack),
+            daily: to_ss!(self.daily),
 
             sel_b_color: to_ss!(self.sel_b_color),
             sel_lang: to_ss!(self.sel_lang),
+            sel_answers: to_ss!(self.sel_answers),
+            sel_timer: to_ss!(self.sel_timer),
+            sel_distr: to_ss!(self.sel_distr),
+            sel_seed: to_ss!(self.sel_seed),
+            random: to_ss!(self.random),
+            d_continent: to_ss!(self.d_continent),
+            d_colours: to_ss!(self.d_colours),
+            d_spelling: to_ss!(self.d_spelling),
         }
     }
 }
\ No newline at end of file